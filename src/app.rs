@@ -1,19 +1,31 @@
-use std::default::Default;
+use std::{cmp::Reverse, collections::BTreeSet, default::Default, fs};
 
+use anyhow::{anyhow, Context};
+use cybercook::{
+    modules::{Category, Module},
+    recipe::{Bake, BakeControl, Recipe, Step},
+};
 use eframe::{
     egui,
-    egui::{Align, Color32, DragAndDrop, Frame, Id, LayerId, Layout, Order, Sense, TextEdit},
+    egui::{
+        Align, CollapsingHeader, Color32, DragAndDrop, DroppedFile, Frame, Id, LayerId, Layout,
+        Order, RichText, TextEdit,
+    },
     emath,
 };
 use log::debug;
+use rfd::FileDialog;
+use strum::IntoEnumIterator;
 
 use crate::{
-    modules::{
-        crypto::aes::AESEncrypt,
-        encoding::base64::{Base64Decoder, Base64Encoder},
-        Module,
+    baker::Baker,
+    views::{
+        buffer::{view_switch, Buffer},
+        copy::copy_menu,
+        module::{ModuleView, StepAction, StepStatus, StepView},
+        search::fuzzy_score,
+        splitter::{Splitter, SplitterAxis},
     },
-    views::splitter::{Splitter, SplitterAxis},
 };
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -24,11 +36,38 @@ struct Location {
 
 pub struct CyberCook {
     pub available_module: Vec<Box<dyn Module>>,
-    pub selected_module: Vec<Box<dyn Module>>,
-    pub input: String,
+    pub recipe: Recipe,
+    pub input: Buffer,
+    /// Result of the last run of `recipe` over `input`.
+    pub bake: Option<Bake>,
+    /// Step chosen with "run up to here".
+    pub run_to: Option<usize>,
+    pub baker: Baker,
+    pub auto_bake: bool,
+    /// Bumped on every change to `recipe`, so spotting edits costs nothing.
+    pub recipe_edits: u64,
+    /// `recipe_edits` and `run_to` of the last bake that was started, or
+    /// `None` if it was cancelled.
+    pub baked: Option<(u64, Option<usize>)>,
+    pub input_changed: bool,
+    /// `bake`'s output, empty if a step failed.
+    pub output: Buffer,
+    /// Last file operation error, shown in the header.
+    pub error: Option<String>,
+    /// Filter typed above the module list.
+    pub search: String,
+    /// Keys of the modules pinned to the top of the module list.
+    pub favourites: BTreeSet<String>,
 }
 
+/// Storage key of [`CyberCook::favourites`].
+const FAVOURITES_KEY: &str = "favourites";
+
 impl eframe::App for CyberCook {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, FAVOURITES_KEY, &self.favourites);
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
             self.ui_header(ctx);
@@ -40,15 +79,31 @@ impl eframe::App for CyberCook {
 }
 
 impl CyberCook {
-    pub fn new(available_module: Vec<Box<dyn Module>>) -> Self {
+    pub fn new(
+        available_module: Vec<Box<dyn Module>>,
+        storage: Option<&dyn eframe::Storage>,
+    ) -> Self {
         Self {
             available_module,
-            selected_module: vec![],
-            input: "".to_string(),
+            recipe: Recipe::default(),
+            input: Buffer::default(),
+            bake: None,
+            run_to: None,
+            baker: Baker::default(),
+            auto_bake: true,
+            recipe_edits: 0,
+            baked: None,
+            input_changed: true,
+            output: Buffer::default(),
+            error: None,
+            search: String::new(),
+            favourites: storage
+                .and_then(|storage| eframe::get_value(storage, FAVOURITES_KEY))
+                .unwrap_or_default(),
         }
     }
 
-    fn ui_header(&self, ctx: &egui::Context) {
+    fn ui_header(&mut self, ctx: &egui::Context) {
         egui::TopBottomPanel::top("header").show(ctx, |ui| {
             // header
             ui.horizontal(|ui| {
@@ -56,13 +111,31 @@ impl CyberCook {
                 ui.label("CyberCook");
                 ui.separator();
                 ui.menu_button("File", |ui| {
-                    if ui.button("Open").clicked() {
-                        println!("Open");
+                    if ui.button("Open Recipe…").clicked() {
+                        ui.close_menu();
+                        let result = self.open_recipe();
+                        self.report(result);
                     }
-                    if ui.button("Save").clicked() {
-                        println!("Save");
+                    if ui.button("Save Recipe…").clicked() {
+                        ui.close_menu();
+                        let result = self.save_recipe();
+                        self.report(result);
+                    }
+                    ui.separator();
+                    if ui.button("Open Input…").clicked() {
+                        ui.close_menu();
+                        let result = self.open_input();
+                        self.report(result);
+                    }
+                    if ui.button("Save Output…").clicked() {
+                        ui.close_menu();
+                        let result = self.save_output();
+                        self.report(result);
                     }
                 });
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
                 ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                     egui::widgets::global_theme_preference_switch(ui);
                 });
@@ -70,38 +143,168 @@ impl CyberCook {
         });
     }
 
+    fn open_recipe(&mut self) -> anyhow::Result<()> {
+        if let Some(path) = FileDialog::new()
+            .add_filter("Recipe", &["json"])
+            .pick_file()
+        {
+            let json = fs::read_to_string(&path)?;
+            self.recipe = Recipe::from_json(&json)?;
+            self.recipe_edits += 1;
+            self.run_to = None;
+        }
+        Ok(())
+    }
+
+    fn save_recipe(&mut self) -> anyhow::Result<()> {
+        if let Some(path) = FileDialog::new()
+            .add_filter("Recipe", &["json"])
+            .set_file_name("recipe.json")
+            .save_file()
+        {
+            fs::write(&path, self.recipe.to_json()?)?;
+        }
+        Ok(())
+    }
+
+    fn open_input(&mut self) -> anyhow::Result<()> {
+        if let Some(path) = FileDialog::new().pick_file() {
+            self.input.set(fs::read(&path)?);
+            self.input_changed = true;
+        }
+        Ok(())
+    }
+
+    /// Writes the output bytes exactly as the last bake produced them.
+    fn save_output(&mut self) -> anyhow::Result<()> {
+        if let Some((index, _)) = self.bake.as_ref().and_then(|bake| bake.error()) {
+            return Err(anyhow!(
+                "step {} failed, there is no output to save",
+                index + 1
+            ));
+        }
+        if let Some(path) = FileDialog::new().set_file_name("output.bin").save_file() {
+            fs::write(&path, self.output.bytes())
+                .with_context(|| format!("writing {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    fn report(&mut self, result: anyhow::Result<()>) {
+        self.error = result.err().map(|e| format!("{:#}", e));
+    }
+
     fn ui_module_list(&mut self, ctx: &egui::Context) {
         let frame = Frame::default().inner_margin(1.0);
         egui::SidePanel::left("module_list")
             .resizable(true)
             .show(ctx, |ui| {
-                // margin top
+                ui.add(
+                    TextEdit::singleline(&mut self.search)
+                        .desired_width(f32::INFINITY)
+                        .hint_text("Search modules"),
+                );
+                let mut removed_step = None;
+                let mut toggled_favourite = None;
                 let (_, dropped_payload) = ui.dnd_drop_zone::<Location, ()>(frame, |ui| {
                     egui::ScrollArea::vertical().show(ui, |ui| {
-                        for (index, module) in self.available_module.iter().enumerate() {
-                            let drag_id = Id::new(module.id());
+                        // `group` keeps drag ids unique for modules listed twice
+                        let mut item =
+                            |ui: &mut egui::Ui, group: &str, row: usize, index: usize| {
+                                let module = &self.available_module[index];
+                                let drag_id = Id::new((module.id(), group));
+                                let pinned = self.favourites.contains(module.key());
+                                let mut favourite = pinned;
 
-                            let item_location = Location { col: 0, row: index };
-                            let response = ui
-                                .dnd_drag_source(drag_id, item_location, |ui| {
-                                    module.render_list(ui, index);
-                                })
-                                .response;
+                                let item_location = Location { col: 0, row: index };
+                                let response = ui
+                                    .dnd_drag_source(drag_id, item_location, |ui| {
+                                        module.render_list(ui, row, &mut favourite);
+                                    })
+                                    .response;
+                                if favourite != pinned {
+                                    toggled_favourite = Some(module.key().to_string());
+                                }
 
-                            if response.hovered() {
-                                if let Some(dragged_payload) =
-                                    response.dnd_release_payload::<Location>()
-                                {
-                                    self.selected_module.remove(dragged_payload.row);
+                                if response.hovered() {
+                                    if let Some(dragged_payload) =
+                                        response.dnd_release_payload::<Location>()
+                                    {
+                                        removed_step = Some(*dragged_payload);
+                                    }
                                 }
+                            };
+
+                        let query = self.search.trim();
+                        if query.is_empty() {
+                            let favourites: Vec<usize> = (0..self.available_module.len())
+                                .filter(|&index| {
+                                    self.favourites.contains(self.available_module[index].key())
+                                })
+                                .collect();
+                            if !favourites.is_empty() {
+                                CollapsingHeader::new("★ Favourites")
+                                    .default_open(true)
+                                    .show(ui, |ui| {
+                                        for (row, &index) in favourites.iter().enumerate() {
+                                            item(ui, "favourites", row, index);
+                                        }
+                                    });
+                            }
+                            for category in Category::iter() {
+                                let members: Vec<usize> = (0..self.available_module.len())
+                                    .filter(|&index| {
+                                        self.available_module[index].category() == category
+                                    })
+                                    .collect();
+                                if members.is_empty() {
+                                    continue;
+                                }
+                                CollapsingHeader::new(category.to_string())
+                                    .default_open(true)
+                                    .show(ui, |ui| {
+                                        for (row, &index) in members.iter().enumerate() {
+                                            item(ui, "category", row, index);
+                                        }
+                                    });
+                            }
+                        } else {
+                            // best match first; a hit in the name counts double
+                            let mut results: Vec<(u32, usize)> = self
+                                .available_module
+                                .iter()
+                                .enumerate()
+                                .filter_map(|(index, module)| {
+                                    let name = fuzzy_score(query, module.name()).map(|s| s * 2);
+                                    let description = fuzzy_score(query, module.description());
+                                    name.max(description).map(|score| (score, index))
+                                })
+                                .collect();
+                            results.sort_by_key(|&(score, _)| Reverse(score));
+                            if results.is_empty() {
+                                ui.label(RichText::new("No matching modules").weak());
+                            }
+                            for (row, &(_, index)) in results.iter().enumerate() {
+                                item(ui, "search", row, index);
                             }
                         }
                     });
 
                     ui.allocate_space(ui.available_size());
                 });
-                if let Some(dragged_payload) = dropped_payload {
-                    self.selected_module.remove(dragged_payload.row);
+                // steps dragged back onto the list are removed from the recipe
+                if let Some(dragged_payload) = removed_step.or(dropped_payload.as_deref().copied())
+                {
+                    if dragged_payload.col == 1 {
+                        self.recipe.steps.remove(dragged_payload.row);
+                        self.recipe_edits += 1;
+                        self.run_to = None;
+                    }
+                }
+                if let Some(key) = toggled_favourite {
+                    if !self.favourites.remove(&key) {
+                        self.favourites.insert(key);
+                    }
                 }
             });
     }
@@ -112,10 +315,20 @@ impl CyberCook {
         // CentralPanel width is 200px
         egui::CentralPanel::default().show(ctx, |ui| {
             let mut dropped_module = None;
+            let mut step_action = None;
             let (_, dropped_payload) = ui.dnd_drop_zone::<Location, ()>(frame, |ui| {
                 egui::ScrollArea::vertical().show(ui, |ui| {
-                    for (index, module) in self.selected_module.iter_mut().enumerate() {
-                        let drag_id = Id::new(module.id());
+                    for (index, step) in self.recipe.steps.iter_mut().enumerate() {
+                        let status = match &self.bake {
+                            Some(bake) if bake.paused_at == Some(index) => StepStatus::Paused,
+                            Some(bake) => bake
+                                .steps
+                                .get(index)
+                                .map_or(StepStatus::NotRun, StepStatus::Ran),
+                            None => StepStatus::NotRun,
+                        };
+                        let run_to = self.run_to == Some(index);
+                        let drag_id = Id::new(step.module.id());
 
                         let item_location = Location { col: 1, row: index };
 
@@ -137,7 +350,9 @@ impl CyberCook {
 
                         let response = if !can_drag {
                             ui.scope(|ui| {
-                                module.render(ui, index);
+                                if let Some(action) = step.render(ui, index, status, run_to) {
+                                    step_action = Some((index, action));
+                                }
                             })
                             .response
                         } else {
@@ -145,7 +360,9 @@ impl CyberCook {
                             let layer_id = LayerId::new(Order::Tooltip, drag_id);
 
                             let response = ui
-                                .with_layer_id(layer_id, |ui| module.render(ui, index))
+                                .with_layer_id(layer_id, |ui| {
+                                    step.render(ui, index, status, run_to)
+                                })
                                 .response;
 
                             if let Some(pointer_pos) = ctx.pointer_interact_pos() {
@@ -196,42 +413,68 @@ impl CyberCook {
                 ui.allocate_space(ui.available_size());
             });
 
+            match step_action {
+                Some((index, StepAction::Duplicate)) => {
+                    let step = self.recipe.steps[index].clone();
+                    self.recipe.steps.insert(index + 1, step);
+                    self.recipe_edits += 1;
+                    self.run_to = None;
+                }
+                Some((index, StepAction::RunTo)) => {
+                    self.run_to = (self.run_to != Some(index)).then_some(index);
+                }
+                Some((_, StepAction::Edited)) => self.recipe_edits += 1,
+                None => {}
+            }
+            if dropped_module.is_some() || dropped_payload.is_some() {
+                self.recipe_edits += 1;
+                self.run_to = None;
+            }
+
             if let Some((dragged_payload, insert_index)) = dropped_module {
                 if dragged_payload.col == 1 {
                     // 上下拖动
-                    let module = self.selected_module.remove(dragged_payload.row);
+                    let step = self.recipe.steps.remove(dragged_payload.row);
                     if dragged_payload.row < insert_index {
-                        self.selected_module.insert(insert_index - 1, module);
+                        self.recipe.steps.insert(insert_index - 1, step);
                     } else {
-                        self.selected_module.insert(insert_index, module);
+                        self.recipe.steps.insert(insert_index, step);
                     }
                 } else if let Some(module) = self.available_module.get(dragged_payload.row) {
-                    self.selected_module
-                        .insert(insert_index, module.clone_box());
+                    self.recipe
+                        .steps
+                        .insert(insert_index, Step::new(module.clone_box()));
                 }
             } else if let Some(dragged_payload) = dropped_payload {
                 if dragged_payload.col == 0 {
                     // 左右拖动
                     if let Some(module) = self.available_module.get(dragged_payload.row) {
-                        self.selected_module.push(module.clone_box());
+                        self.recipe.steps.push(Step::new(module.clone_box()));
                     }
                 } else {
                     // 上下拖动
-                    let module = self.selected_module.remove(dragged_payload.row);
-                    self.selected_module.push(module);
+                    let step = self.recipe.steps.remove(dragged_payload.row);
+                    self.recipe.steps.push(step);
                 }
             }
         });
     }
 
     fn ui_input_output(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
-        let Self { input, .. } = self;
-        let font_size = ctx
-            .style()
-            .text_styles
-            .get(&egui::TextStyle::Body)
-            .unwrap()
-            .size;
+        let Self {
+            input,
+            recipe,
+            bake,
+            run_to,
+            baker,
+            auto_bake,
+            recipe_edits,
+            baked,
+            input_changed,
+            output,
+            error,
+            ..
+        } = self;
 
         egui::SidePanel::right("input_output")
             .min_width(ui.available_width() / 2.0)
@@ -240,50 +483,154 @@ impl CyberCook {
             .show(ctx, |ui| {
                 Splitter::new("input_output", SplitterAxis::Vertical).show(ui, |up_ui, down_ui| {
                     // Input 区域
+                    let input_rect = up_ui.max_rect();
                     up_ui.vertical(|ui| {
-                        ui.heading("Input");
+                        ui.horizontal(|ui| {
+                            ui.heading("Input");
+                            ui.label(format!("{} bytes", input.bytes().len()));
+                            if ui.button("Clear").clicked() {
+                                input.set(Vec::new());
+                                *input_changed = true;
+                            }
+                            view_switch(ui, &mut input.view);
+                        });
                         egui::ScrollArea::vertical()
                             .id_salt("input")
                             .show(ui, |ui| {
-                                ui.add(
-                                    TextEdit::multiline(input)
-                                        .desired_width(ui.available_width())
-                                        .desired_rows((ui.available_height() / font_size) as _)
-                                        .hint_text("Input here"),
-                                );
+                                if input.show(ui, "Input here, or drop a file", true) {
+                                    *input_changed = true;
+                                }
                             })
                     });
 
-                    let mut output = input.as_bytes().to_vec();
-                    for module in self.selected_module.iter() {
-                        match module.process(&output) {
-                            Ok(new_output) => {
-                                output = new_output;
-                            }
-                            Err(e) => {
-                                output = format!("{}", e).as_bytes().to_vec();
-                                break;
+                    // files dropped onto the input area replace the input;
+                    // some platforms report no pointer position while dragging
+                    let (hovering, dropped, pointer) = ctx.input(|i| {
+                        (
+                            !i.raw.hovered_files.is_empty(),
+                            i.raw.dropped_files.first().cloned(),
+                            i.pointer.hover_pos(),
+                        )
+                    });
+                    let over_input = pointer.is_none_or(|pos| input_rect.contains(pos));
+                    if hovering && over_input {
+                        let stroke = up_ui.visuals().selection.stroke;
+                        up_ui.painter().rect_stroke(input_rect, 2.0, stroke);
+                    }
+                    if let Some(file) = dropped.filter(|_| over_input) {
+                        match read_dropped_file(&file) {
+                            Ok(bytes) => {
+                                input.set(bytes);
+                                *input_changed = true;
+                                *error = None;
                             }
+                            Err(e) => *error = Some(format!("{:#}", e)),
                         }
                     }
 
-                    let mut output = String::from_utf8_lossy(&output).to_string();
+                    // only bake again when the input or the recipe changed
+                    let signature = (*recipe_edits, *run_to);
+                    let stale = *input_changed || baked.as_ref() != Some(&signature);
 
                     // Output 区域
                     down_ui.vertical(|ui| {
-                        ui.heading("Output");
+                        ui.horizontal(|ui| {
+                            ui.heading("Output");
+                            let bake_clicked = ui.button("Bake").clicked();
+                            ui.checkbox(auto_bake, "Auto bake");
+                            view_switch(ui, &mut output.view);
+                            ui.menu_button("Copy", |ui| copy_menu(ui, output.bytes()));
+                            if bake_clicked || (stale && *auto_bake) {
+                                baker.start(
+                                    ctx,
+                                    recipe,
+                                    input.bytes().to_vec(),
+                                    *run_to,
+                                    BakeControl::default(),
+                                );
+                                *baked = Some(signature);
+                                *input_changed = false;
+                            }
+                            if let Some(result) = baker.poll() {
+                                output.set(match result.error() {
+                                    Some(_) => Vec::new(),
+                                    None => result.output.clone(),
+                                });
+                                *bake = Some(result);
+                            }
+
+                            if let Some((completed, steps)) = baker.progress() {
+                                ui.spinner();
+                                ui.label(format!("Step {}/{}", completed, steps));
+                                if ui.button("Cancel").clicked() {
+                                    baker.cancel();
+                                    *baked = None;
+                                    // auto bake would start it again straight away
+                                    *auto_bake = false;
+                                }
+                            } else if stale && !*auto_bake {
+                                ui.colored_label(ui.visuals().warn_fg_color, "Out of date");
+                            } else if let Some((index, e)) =
+                                bake.as_ref().and_then(|bake| bake.error())
+                            {
+                                let name = recipe
+                                    .steps
+                                    .get(index)
+                                    .map_or("", |step| step.module.name());
+                                ui.colored_label(
+                                    ui.visuals().error_fg_color,
+                                    format!("Step {} ({}) failed: {}", index + 1, name, e),
+                                );
+                            } else if let Some(index) =
+                                bake.as_ref().and_then(|bake| bake.paused_at)
+                            {
+                                ui.colored_label(
+                                    ui.visuals().warn_fg_color,
+                                    format!("Paused before step {}", index + 1),
+                                );
+                                // a pause at the end of "run up to here" continues to the end
+                                let resume_to = if ui
+                                    .button("Continue")
+                                    .on_hover_text("Run on to the next breakpoint")
+                                    .clicked()
+                                {
+                                    Some(run_to.filter(|&end| index <= end))
+                                } else if ui
+                                    .button("Step")
+                                    .on_hover_text("Run this step and pause again")
+                                    .clicked()
+                                {
+                                    Some(Some(index))
+                                } else {
+                                    None
+                                };
+                                if let Some(resume_to) = resume_to {
+                                    baker.start(
+                                        ctx,
+                                        recipe,
+                                        input.bytes().to_vec(),
+                                        resume_to,
+                                        BakeControl::resume(index),
+                                    );
+                                }
+                            }
+                        });
                         egui::ScrollArea::vertical()
                             .id_salt("output")
                             .show(ui, |ui| {
-                                ui.add(
-                                    TextEdit::multiline(&mut output)
-                                        .desired_width(ui.available_width())
-                                        .desired_rows((ui.available_height() / font_size) as _)
-                                        .hint_text("Output here"),
-                                );
+                                output.show(ui, "Output here", false);
                             });
                     });
                 });
             });
     }
 }
+
+/// Reads a file dropped onto the window; web builds get the bytes directly.
+fn read_dropped_file(file: &DroppedFile) -> anyhow::Result<Vec<u8>> {
+    match (&file.path, &file.bytes) {
+        (Some(path), _) => fs::read(path).with_context(|| format!("reading {}", path.display())),
+        (None, Some(bytes)) => Ok(bytes.to_vec()),
+        (None, None) => Err(anyhow!("dropped file \"{}\" has no contents", file.name)),
+    }
+}
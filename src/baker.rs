@@ -0,0 +1,80 @@
+use std::{
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use cybercook::recipe::{Bake, BakeControl, Recipe};
+use eframe::egui;
+
+/// Runs recipes on a worker thread so the UI stays responsive.
+#[derive(Default)]
+pub struct Baker {
+    job: Option<Job>,
+}
+
+struct Job {
+    control: BakeControl,
+    steps: usize,
+    receiver: Receiver<Option<Bake>>,
+}
+
+impl Baker {
+    /// Starts baking a snapshot of `recipe`, cancelling any bake in flight.
+    /// `control` is [`BakeControl::resume`] to continue after a pause.
+    pub fn start(
+        &mut self,
+        ctx: &egui::Context,
+        recipe: &Recipe,
+        input: Vec<u8>,
+        run_to: Option<usize>,
+        control: BakeControl,
+    ) {
+        self.cancel();
+
+        let recipe = recipe.clone();
+        let (sender, receiver) = mpsc::channel();
+        self.job = Some(Job {
+            control: control.clone(),
+            steps: run_to.map_or(recipe.steps.len(), |index| index + 1),
+            receiver,
+        });
+
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let bake = recipe.bake_debug(&input, run_to, &control);
+            // the receiver is gone if the job was replaced meanwhile
+            let _ = sender.send(bake);
+            ctx.request_repaint();
+        });
+    }
+
+    pub fn cancel(&mut self) {
+        if let Some(job) = self.job.take() {
+            job.control.cancel();
+        }
+    }
+
+    /// Takes the finished bake, if the running job is done.
+    pub fn poll(&mut self) -> Option<Bake> {
+        let job = self.job.as_ref()?;
+        match job.receiver.try_recv() {
+            Ok(bake) => {
+                self.job = None;
+                bake
+            }
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                // a module panicked on the worker thread
+                self.job = None;
+                None
+            }
+        }
+    }
+
+    /// `(finished steps, total steps)` of the running job.
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        self.job
+            .as_ref()
+            .map(|job| (job.control.completed(), job.steps))
+    }
+}
@@ -0,0 +1,194 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use anyhow::{anyhow, bail, Context};
+use clap::Parser;
+use cybercook::{
+    modules::{self, args},
+    recipe::Recipe,
+};
+
+/// Run a saved CyberCook recipe over stdin, a file or a directory of files.
+#[derive(Parser)]
+#[command(name = "bake", version)]
+struct Args {
+    /// Recipe file (JSON), or comma-separated module ids to run with their
+    /// default arguments, e.g. "base64-decode,base64-decode"
+    #[arg(required_unless_present = "list")]
+    recipe: Option<PathBuf>,
+
+    /// Input file or directory; reads stdin when omitted or "-"
+    input: Option<PathBuf>,
+
+    /// Output file, or output directory when the input is a directory;
+    /// writes to stdout when omitted
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Set an argument as NAME=VALUE on every step that has it, or as
+    /// STEP.NAME=VALUE on one step (counting from 1). Bytes take a type
+    /// prefix such as hex: or file:, see --list
+    #[arg(short, long, value_name = "[STEP.]NAME=VALUE")]
+    arg: Vec<String>,
+
+    /// List the available module ids and their arguments, then exit
+    #[arg(long)]
+    list: bool,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("bake: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Returns `Ok(false)` when a step failed on any of the inputs.
+fn run(args: &Args) -> anyhow::Result<bool> {
+    if args.list {
+        // e.g. `bake --list | head` closing the pipe early is not an error
+        return match list() {
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(true),
+            result => result.map(|_| true).map_err(Into::into),
+        };
+    }
+    let mut recipe = load_recipe(args.recipe.as_deref().unwrap_or(Path::new("")))?;
+    for spec in &args.arg {
+        set_arg(&mut recipe, spec).with_context(|| format!("--arg {}", spec))?;
+    }
+
+    match &args.input {
+        Some(input) if input.is_dir() => {
+            let output_dir = args
+                .output
+                .as_ref()
+                .ok_or_else(|| anyhow!("--output directory is required for a directory input"))?;
+            fs::create_dir_all(output_dir)
+                .with_context(|| format!("creating {}", output_dir.display()))?;
+
+            let mut entries = fs::read_dir(input)
+                .with_context(|| format!("reading {}", input.display()))?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<io::Result<Vec<_>>>()?;
+            entries.sort();
+
+            let mut ok = true;
+            for path in entries.iter().filter(|p| p.is_file()) {
+                let data = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+                let target = output_dir.join(path.file_name().unwrap_or_default());
+                ok &= bake_one(&recipe, &data, &path.display().to_string(), Some(&target))?;
+            }
+            Ok(ok)
+        }
+        Some(input) if input.as_os_str() != "-" => {
+            let data = fs::read(input).with_context(|| format!("reading {}", input.display()))?;
+            bake_one(
+                &recipe,
+                &data,
+                &input.display().to_string(),
+                args.output.as_deref(),
+            )
+        }
+        _ => {
+            let mut data = Vec::new();
+            io::stdin().read_to_end(&mut data)?;
+            bake_one(&recipe, &data, "<stdin>", args.output.as_deref())
+        }
+    }
+}
+
+fn load_recipe(path: &Path) -> anyhow::Result<Recipe> {
+    if !path.exists() {
+        if let Some(keys) = path.to_str() {
+            return Recipe::from_keys(keys.split(','))
+                .with_context(|| format!("no recipe file {}", path.display()));
+        }
+    }
+    let json =
+        fs::read_to_string(path).with_context(|| format!("reading recipe {}", path.display()))?;
+    Recipe::from_json(&json).with_context(|| format!("loading recipe {}", path.display()))
+}
+
+fn set_arg(recipe: &mut Recipe, spec: &str) -> anyhow::Result<()> {
+    let (target, value) = spec
+        .split_once('=')
+        .ok_or_else(|| anyhow!("expected NAME=VALUE"))?;
+    let (only, name) = match target.split_once('.') {
+        Some((step, name)) => {
+            let step = step
+                .parse::<usize>()
+                .ok()
+                .filter(|step| (1..=recipe.steps.len()).contains(step))
+                .ok_or_else(|| anyhow!("no step {}", step))?;
+            (Some(step - 1), name)
+        }
+        None => (None, target),
+    };
+
+    let mut found = false;
+    for (index, step) in recipe.steps.iter_mut().enumerate() {
+        if only.is_some_and(|only| only != index) {
+            continue;
+        }
+        found |= args::set(step.module.as_mut(), name, value)
+            .with_context(|| format!("step {} ({})", index + 1, step.module.name()))?;
+    }
+    if !found {
+        bail!("no step has an argument \"{}\"", name);
+    }
+    Ok(())
+}
+
+/// Prints every module id with its arguments.
+fn list() -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    for mut module in modules::all() {
+        writeln!(
+            out,
+            "{:<16}{:<14}{}",
+            module.key(),
+            module.category(),
+            module.name()
+        )?;
+        for arg in module.args() {
+            writeln!(out, "    {:<12}{}: {}", arg.name, arg.label, arg.describe())?;
+        }
+    }
+    out.flush()
+}
+
+fn bake_one(
+    recipe: &Recipe,
+    input: &[u8],
+    source: &str,
+    output: Option<&Path>,
+) -> anyhow::Result<bool> {
+    let bake = recipe.bake(input);
+    if let Some((index, e)) = bake.error() {
+        eprintln!(
+            "bake: {}: step {} ({}) failed: {}",
+            source,
+            index + 1,
+            recipe.steps[index].module.name(),
+            e
+        );
+        return Ok(false);
+    }
+
+    match output {
+        Some(path) => {
+            fs::write(path, &bake.output).with_context(|| format!("writing {}", path.display()))?
+        }
+        None => io::stdout().write_all(&bake.output)?,
+    }
+    Ok(true)
+}
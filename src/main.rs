@@ -1,15 +1,10 @@
+use cybercook::modules;
 use eframe::{egui, egui::Visuals};
 
-use crate::{
-    app::CyberCook,
-    modules::{
-        crypto::aes::{AESDecrypt, AESEncrypt},
-        encoding::base64::{Base64Decoder, Base64Encoder},
-    },
-};
+use crate::app::CyberCook;
 
 mod app;
-mod modules;
+mod baker;
 mod views;
 
 fn main() -> eframe::Result {
@@ -27,12 +22,7 @@ fn main() -> eframe::Result {
         options,
         Box::new(|cc| {
             cc.egui_ctx.set_visuals(Visuals::dark());
-            Ok(Box::new(CyberCook::new(vec![
-                Box::<Base64Encoder>::default(),
-                Box::<Base64Decoder>::default(),
-                Box::<AESEncrypt>::default(),
-                Box::<AESDecrypt>::default(),
-            ])))
+            Ok(Box::new(CyberCook::new(modules::all(), cc.storage)))
         }),
     )
 }
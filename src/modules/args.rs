@@ -0,0 +1,384 @@
+use std::{fmt::Display, ops::RangeInclusive};
+
+use anyhow::{anyhow, bail, Context};
+#[cfg(feature = "gui")]
+use eframe::egui::{ComboBox, DragValue, Grid, Id, Response, RichText, Ui};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use strum::IntoEnumIterator;
+
+use crate::modules::{
+    input::{Argument, InputType},
+    Module,
+};
+
+/// One argument of a module, borrowing the field that holds its value.
+/// [`Module::args`](crate::modules::Module::args) returns them in display
+/// order; the UI, recipe files and the `bake` CLI are built from that list.
+pub struct Arg<'a> {
+    /// Key in recipe files and for `bake --arg`.
+    pub name: &'static str,
+    pub label: &'static str,
+    pub hint: Option<&'static str>,
+    /// Hidden arguments do not apply to the current configuration. They are
+    /// still saved, so switching back restores them.
+    pub shown: bool,
+    pub kind: Kind<'a>,
+}
+
+pub enum Kind<'a> {
+    /// A byte string typed as text, hex or Base64.
+    Bytes(&'a mut Argument),
+    /// One variant of a fieldless enum.
+    Enum(&'a mut dyn Choice),
+    Int(&'a mut usize, RangeInclusive<usize>),
+    Bool(&'a mut bool),
+    /// One value out of a list that may depend on other arguments.
+    Options(&'a mut usize, &'static [usize]),
+    /// Free text; `true` for a multi-line editor.
+    Text(&'a mut String, bool),
+}
+
+/// A fieldless enum offered as a drop-down. Implemented for every enum
+/// deriving `EnumIter`, `Display` and serde's traits.
+pub trait Choice {
+    /// Display names of all variants.
+    fn options(&self) -> Vec<String>;
+    fn selected(&self) -> usize;
+    fn select(&mut self, index: usize);
+    fn to_json(&self) -> anyhow::Result<Value>;
+    fn set_json(&mut self, value: Value) -> anyhow::Result<()>;
+}
+
+impl<T> Choice for T
+where
+    T: IntoEnumIterator + Display + PartialEq + Copy + Serialize + DeserializeOwned,
+{
+    fn options(&self) -> Vec<String> {
+        T::iter().map(|variant| variant.to_string()).collect()
+    }
+
+    fn selected(&self) -> usize {
+        T::iter().position(|variant| variant == *self).unwrap_or(0)
+    }
+
+    fn select(&mut self, index: usize) {
+        if let Some(variant) = T::iter().nth(index) {
+            *self = variant;
+        }
+    }
+
+    fn to_json(&self) -> anyhow::Result<Value> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    fn set_json(&mut self, value: Value) -> anyhow::Result<()> {
+        *self = serde_json::from_value(value)?;
+        Ok(())
+    }
+}
+
+/// Feedback on an argument's current value, shown next to it so problems
+/// are visible before baking.
+pub struct Note {
+    /// [`Arg::name`] of the argument.
+    pub arg: &'static str,
+    /// `Err` for a value that will make the step fail.
+    pub text: Result<String, String>,
+}
+
+impl Note {
+    pub fn new(arg: &'static str, text: Result<String, String>) -> Self {
+        Self { arg, text }
+    }
+}
+
+impl<'a> Arg<'a> {
+    pub fn new(name: &'static str, label: &'static str, kind: Kind<'a>) -> Self {
+        Self {
+            name,
+            label,
+            hint: None,
+            shown: true,
+            kind,
+        }
+    }
+
+    pub fn hint(mut self, hint: &'static str) -> Self {
+        self.hint = Some(hint);
+        self
+    }
+
+    pub fn shown_if(mut self, shown: bool) -> Self {
+        self.shown = shown;
+        self
+    }
+
+    /// What the argument accepts, for `bake --list`.
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            Kind::Bytes(_) => format!(
+                "bytes, optionally prefixed with {}",
+                InputType::iter()
+                    .map(InputType::prefix)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            Kind::Enum(choice) => format!("one of {}", choice.options().join(", ")),
+            Kind::Int(_, range) => format!("integer {}..={}", range.start(), range.end()),
+            Kind::Bool(_) => "true or false".to_string(),
+            Kind::Options(_, options) => format!(
+                "one of {}",
+                options
+                    .iter()
+                    .map(|option| option.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Kind::Text(..) => "text".to_string(),
+        }
+    }
+
+    fn to_json(&self) -> anyhow::Result<Value> {
+        Ok(match &self.kind {
+            Kind::Bytes(value) => serde_json::to_value(&**value)?,
+            Kind::Enum(choice) => choice.to_json()?,
+            Kind::Int(value, _) | Kind::Options(value, _) => Value::from(**value),
+            Kind::Bool(value) => Value::Bool(**value),
+            Kind::Text(value, _) => Value::String(value.to_string()),
+        })
+    }
+
+    fn set_json(&mut self, value: Value) -> anyhow::Result<()> {
+        match &mut self.kind {
+            Kind::Bytes(argument) => **argument = serde_json::from_value(value)?,
+            Kind::Enum(choice) => choice.set_json(value)?,
+            Kind::Int(..) | Kind::Options(..) => {
+                let number = value
+                    .as_u64()
+                    .ok_or_else(|| anyhow!("expected an integer, got {}", value))?;
+                self.set_int(number as usize)?;
+            }
+            Kind::Bool(flag) => {
+                **flag = value
+                    .as_bool()
+                    .ok_or_else(|| anyhow!("expected true or false, got {}", value))?
+            }
+            Kind::Text(text, _) => {
+                **text = value
+                    .as_str()
+                    .ok_or_else(|| anyhow!("expected a string, got {}", value))?
+                    .to_string()
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a command line value.
+    pub fn set_str(&mut self, value: &str) -> anyhow::Result<()> {
+        match &mut self.kind {
+            Kind::Bytes(argument) => **argument = Argument::parse(value),
+            Kind::Enum(choice) => {
+                let options = choice.options();
+                match options
+                    .iter()
+                    .position(|option| option.eq_ignore_ascii_case(value))
+                {
+                    Some(index) => choice.select(index),
+                    // the name used in recipe files also works
+                    None => choice
+                        .set_json(Value::String(value.to_string()))
+                        .map_err(|_| anyhow!("expected one of {}", options.join(", ")))?,
+                }
+            }
+            Kind::Int(..) | Kind::Options(..) => {
+                let number = value
+                    .parse()
+                    .map_err(|_| anyhow!("expected an integer, got \"{}\"", value))?;
+                self.set_int(number)?;
+            }
+            Kind::Bool(flag) => {
+                **flag = match value.to_ascii_lowercase().as_str() {
+                    "true" | "yes" | "1" => true,
+                    "false" | "no" | "0" => false,
+                    _ => bail!("expected true or false, got \"{}\"", value),
+                }
+            }
+            Kind::Text(text, _) => **text = value.to_string(),
+        }
+        Ok(())
+    }
+
+    fn set_int(&mut self, number: usize) -> anyhow::Result<()> {
+        match &mut self.kind {
+            Kind::Int(value, range) if range.contains(&number) => **value = number,
+            Kind::Int(_, range) => bail!(
+                "{} is out of range {}..={}",
+                number,
+                range.start(),
+                range.end()
+            ),
+            Kind::Options(value, options) if options.contains(&number) => **value = number,
+            Kind::Options(..) => bail!("{} is not allowed, expected {}", number, self.describe()),
+            _ => unreachable!("set_int on a non-integer argument"),
+        }
+        Ok(())
+    }
+}
+
+/// Arguments as a JSON object keyed by [`Arg::name`], or `null` when there
+/// are none.
+pub fn to_json(args: Vec<Arg>) -> anyhow::Result<Value> {
+    if args.is_empty() {
+        return Ok(Value::Null);
+    }
+    let mut object = Map::new();
+    for arg in args {
+        object.insert(arg.name.to_string(), arg.to_json()?);
+    }
+    Ok(Value::Object(object))
+}
+
+/// Applies an object produced by [`to_json`] to `module`. Missing
+/// arguments keep their value; unknown ones are an error.
+pub fn from_json<M: Module + ?Sized>(module: &mut M, value: Value) -> anyhow::Result<()> {
+    let mut object = match value {
+        Value::Null => return Ok(()),
+        Value::Object(object) => object,
+        other => bail!("expected an object of arguments, got {}", other),
+    };
+    // in declaration order, listing the arguments again after each one, as
+    // options may depend on earlier arguments (e.g. tag lengths on the mode)
+    let names: Vec<_> = module.args().iter().map(|arg| arg.name).collect();
+    for name in names {
+        if let Some(value) = object.remove(name) {
+            let mut args = module.args();
+            let arg = args.iter_mut().find(|arg| arg.name == name);
+            if let Some(arg) = arg {
+                arg.set_json(value)
+                    .with_context(|| format!("argument \"{}\"", name))?;
+            }
+        }
+    }
+    if let Some(name) = object.keys().next() {
+        bail!("unknown argument \"{}\"", name);
+    }
+    keep_options(module.args());
+    Ok(())
+}
+
+/// Sets the argument called `name` of `module` from a command line value.
+/// Returns `false` if there is no such argument.
+pub fn set<M: Module + ?Sized>(module: &mut M, name: &str, value: &str) -> anyhow::Result<bool> {
+    match module.args().into_iter().find(|arg| arg.name == name) {
+        Some(mut arg) => {
+            arg.set_str(value)
+                .with_context(|| format!("argument \"{}\"", name))?;
+        }
+        None => return Ok(false),
+    }
+    keep_options(module.args());
+    Ok(true)
+}
+
+/// Moves [`Kind::Options`] values that are no longer in their list, after
+/// the argument the list depends on changed, to the last option.
+fn keep_options(args: Vec<Arg>) {
+    for arg in args {
+        if let Kind::Options(value, options) = arg.kind {
+            keep_option(value, options);
+        }
+    }
+}
+
+fn keep_option(value: &mut usize, options: &[usize]) {
+    if !options.contains(value) {
+        if let Some(&last) = options.last() {
+            *value = last;
+        }
+    }
+}
+
+/// A two-column grid with one row per shown argument, followed by its note.
+/// Returns whether any value was changed.
+#[cfg(feature = "gui")]
+pub fn show(ui: &mut Ui, id: &str, args: Vec<Arg>, notes: Vec<Note>) -> bool {
+    if !args.iter().any(|arg| arg.shown) {
+        return false;
+    }
+    let mut changed = false;
+    Grid::new(Id::new((id, "grid")))
+        .striped(true)
+        .num_columns(2)
+        .show(ui, |ui| {
+            for arg in args.into_iter().filter(|arg| arg.shown) {
+                let label = ui.label(arg.label);
+                let note = notes.iter().find(|note| note.arg == arg.name);
+                ui.horizontal(|ui| {
+                    let response = show_value(ui, Id::new((id, arg.name)), arg.kind);
+                    changed |= response.changed();
+                    if let Some(hint) = arg.hint {
+                        label.on_hover_text(hint);
+                        response.on_hover_text(hint);
+                    }
+                    match note.map(|note| &note.text) {
+                        Some(Ok(text)) => {
+                            ui.label(RichText::new(text).weak());
+                        }
+                        Some(Err(text)) => {
+                            ui.colored_label(ui.visuals().error_fg_color, text);
+                        }
+                        None => {}
+                    }
+                });
+                ui.end_row();
+            }
+        });
+    changed
+}
+
+/// The value's widget, marked as changed whenever the value changed.
+#[cfg(feature = "gui")]
+fn show_value(ui: &mut Ui, id: Id, kind: Kind) -> Response {
+    match kind {
+        Kind::Bytes(argument) => argument.show(ui),
+        Kind::Enum(choice) => {
+            let options = choice.options();
+            let mut selected = choice.selected();
+            let mut response = ComboBox::from_id_salt(id)
+                .selected_text(&options[selected])
+                .show_ui(ui, |ui| {
+                    for (index, option) in options.iter().enumerate() {
+                        ui.selectable_value(&mut selected, index, option);
+                    }
+                })
+                .response;
+            if selected != choice.selected() {
+                choice.select(selected);
+                response.mark_changed();
+            }
+            response
+        }
+        Kind::Int(value, range) => ui.add(DragValue::new(value).range(range)),
+        Kind::Bool(value) => ui.checkbox(value, ""),
+        Kind::Options(value, options) => {
+            let previous = *value;
+            // keep the value valid when the list depends on another argument
+            keep_option(value, options);
+            let mut response = ComboBox::from_id_salt(id)
+                .selected_text(value.to_string())
+                .show_ui(ui, |ui| {
+                    for &option in options {
+                        ui.selectable_value(value, option, option.to_string());
+                    }
+                })
+                .response;
+            if *value != previous {
+                response.mark_changed();
+            }
+            response
+        }
+        Kind::Text(value, true) => ui.text_edit_multiline(value),
+        Kind::Text(value, false) => ui.text_edit_singleline(value),
+    }
+}
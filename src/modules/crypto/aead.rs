@@ -0,0 +1,439 @@
+use aes::cipher::{BlockEncrypt, KeyInit, Unsigned};
+use aes_gcm::{
+    aead::{consts, Aead, Payload},
+    AesGcm,
+};
+use aes_gcm_siv::{Aes128GcmSiv, Aes256GcmSiv};
+use anyhow::anyhow;
+use ccm::Ccm;
+use cmac::{Cmac, Mac};
+use eax::Eax;
+use uuid::Uuid;
+
+use crate::modules::{
+    args::{Arg, Kind},
+    crypto::{aes::aes_ctr, AeadMode, CounterWidth, Endianness},
+    input::Argument,
+    register_module, Category, Module,
+};
+
+/// Instantiates `$body` with `$C` bound to the AES variant selected by the
+/// key length.
+macro_rules! with_aes {
+    ($key_len:expr, |$C:ident| $body:expr) => {
+        match $key_len {
+            16 => {
+                type $C = aes::Aes128;
+                $body
+            }
+            24 => {
+                type $C = aes::Aes192;
+                $body
+            }
+            32 => {
+                type $C = aes::Aes256;
+                $body
+            }
+            n => Err(anyhow!(
+                "Invalid key length {}, expected 16, 24 or 32 bytes",
+                n
+            )),
+        }
+    };
+}
+
+/// Instantiates `$body` with `$T` bound to the typenum matching `$len`.
+macro_rules! with_size {
+    ($len:expr, $what:literal, [$($n:literal => $U:ident),+], |$T:ident| $body:expr) => {
+        match $len {
+            $($n => {
+                type $T = consts::$U;
+                $body
+            })+
+            n => Err(anyhow!(concat!("Unsupported ", $what, " length {}"), n)),
+        }
+    };
+}
+
+/// Encrypt output and decrypt input are laid out as `ciphertext || tag`.
+#[derive(Clone)]
+pub struct AEADEncrypt {
+    id: String,
+    mode: AeadMode,
+    key: Argument,
+    nonce: Argument,
+    aad: Argument,
+    tag_length: usize,
+}
+
+#[derive(Clone)]
+pub struct AEADDecrypt {
+    id: String,
+    mode: AeadMode,
+    key: Argument,
+    nonce: Argument,
+    aad: Argument,
+    tag_length: usize,
+    verify: bool,
+}
+
+impl Default for AEADEncrypt {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            mode: AeadMode::Gcm,
+            key: Argument::default(),
+            nonce: Argument::default(),
+            aad: Argument::default(),
+            tag_length: 16,
+        }
+    }
+}
+
+impl Default for AEADDecrypt {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            mode: AeadMode::Gcm,
+            key: Argument::default(),
+            nonce: Argument::default(),
+            aad: Argument::default(),
+            tag_length: 16,
+            verify: true,
+        }
+    }
+}
+
+register_module!(AEADEncrypt, "aead-encrypt");
+
+impl Module for AEADEncrypt {
+    fn key(&self) -> &'static str {
+        "aead-encrypt"
+    }
+
+    fn name(&self) -> &str {
+        "AEAD Encrypt"
+    }
+
+    fn description(&self) -> &str {
+        "Encrypt and authenticate input with AES-GCM/CCM/GCM-SIV/EAX"
+    }
+
+    fn category(&self) -> Category {
+        Category::Crypto
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let key = self.key.try_to_vec("Key")?;
+        let nonce = self.nonce.try_to_vec("Nonce")?;
+        let aad = self.aad.try_to_vec("AAD")?;
+        aead_apply(
+            Direction::Seal,
+            self.mode,
+            input,
+            &key,
+            &nonce,
+            &aad,
+            self.tag_length,
+        )
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        aead_args(
+            &mut self.mode,
+            &mut self.key,
+            &mut self.nonce,
+            &mut self.aad,
+            &mut self.tag_length,
+        )
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+register_module!(AEADDecrypt, "aead-decrypt");
+
+impl Module for AEADDecrypt {
+    fn key(&self) -> &'static str {
+        "aead-decrypt"
+    }
+
+    fn name(&self) -> &str {
+        "AEAD Decrypt"
+    }
+
+    fn description(&self) -> &str {
+        "Decrypt and verify input with AES-GCM/CCM/GCM-SIV/EAX"
+    }
+
+    fn category(&self) -> Category {
+        Category::Crypto
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let key = self.key.try_to_vec("Key")?;
+        let nonce = self.nonce.try_to_vec("Nonce")?;
+        if !self.verify {
+            return open_unverified(self.mode, input, &key, &nonce, self.tag_length);
+        }
+        let aad = self.aad.try_to_vec("AAD")?;
+        aead_apply(
+            Direction::Open,
+            self.mode,
+            input,
+            &key,
+            &nonce,
+            &aad,
+            self.tag_length,
+        )
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        let mut args = aead_args(
+            &mut self.mode,
+            &mut self.key,
+            &mut self.nonce,
+            &mut self.aad,
+            &mut self.tag_length,
+        );
+        args.push(
+            Arg::new("verify", "Verify tag", Kind::Bool(&mut self.verify))
+                .hint("Uncheck to decrypt without checking the tag"),
+        );
+        args
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+/// Arguments shared by [`AEADEncrypt`] and [`AEADDecrypt`].
+fn aead_args<'a>(
+    mode: &'a mut AeadMode,
+    key: &'a mut Argument,
+    nonce: &'a mut Argument,
+    aad: &'a mut Argument,
+    tag_length: &'a mut usize,
+) -> Vec<Arg<'a>> {
+    let tag_lengths = mode.tag_lengths();
+    vec![
+        Arg::new("mode", "Mode", Kind::Enum(mode)),
+        Arg::new("key", "Key", Kind::Bytes(key)),
+        Arg::new("nonce", "Nonce", Kind::Bytes(nonce)),
+        Arg::new("aad", "AAD", Kind::Bytes(aad)),
+        Arg::new(
+            "tag_length",
+            "Tag length",
+            Kind::Options(tag_length, tag_lengths),
+        ),
+    ]
+}
+
+fn check_tag_length(mode: AeadMode, tag_length: usize) -> anyhow::Result<()> {
+    if !mode.tag_lengths().contains(&tag_length) {
+        return Err(anyhow!(
+            "{} does not support a {} byte tag, expected one of {:?}",
+            mode,
+            tag_length,
+            mode.tag_lengths()
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Seal,
+    Open,
+}
+
+fn aead_apply(
+    direction: Direction,
+    mode: AeadMode,
+    input: &[u8],
+    key: &[u8],
+    nonce: &[u8],
+    aad: &[u8],
+    tag_length: usize,
+) -> anyhow::Result<Vec<u8>> {
+    check_tag_length(mode, tag_length)?;
+    match mode {
+        AeadMode::Gcm => with_aes!(key.len(), |C| with_size!(
+            tag_length,
+            "tag",
+            [12 => U12, 13 => U13, 14 => U14, 15 => U15, 16 => U16],
+            |T| aead_run::<AesGcm<C, consts::U12, T>>(direction, input, key, nonce, aad)
+        )),
+        AeadMode::Ccm => with_aes!(key.len(), |C| with_size!(
+            tag_length,
+            "tag",
+            [4 => U4, 6 => U6, 8 => U8, 10 => U10, 12 => U12, 14 => U14, 16 => U16],
+            |T| with_size!(
+                nonce.len(),
+                "nonce",
+                [7 => U7, 8 => U8, 9 => U9, 10 => U10, 11 => U11, 12 => U12, 13 => U13],
+                |N| aead_run::<Ccm<C, T, N>>(direction, input, key, nonce, aad)
+            )
+        )),
+        AeadMode::GcmSiv => match key.len() {
+            16 => aead_run::<Aes128GcmSiv>(direction, input, key, nonce, aad),
+            32 => aead_run::<Aes256GcmSiv>(direction, input, key, nonce, aad),
+            n => Err(anyhow!("Invalid key length {}, expected 16 or 32 bytes", n)),
+        },
+        AeadMode::Eax => with_aes!(key.len(), |C| with_size!(
+            tag_length,
+            "tag",
+            [
+                4 => U4, 5 => U5, 6 => U6, 7 => U7, 8 => U8, 9 => U9, 10 => U10,
+                11 => U11, 12 => U12, 13 => U13, 14 => U14, 15 => U15, 16 => U16
+            ],
+            |T| aead_run::<Eax<C, T>>(direction, input, key, nonce, aad)
+        )),
+    }
+}
+
+fn aead_run<A>(
+    direction: Direction,
+    input: &[u8],
+    key: &[u8],
+    nonce: &[u8],
+    aad: &[u8],
+) -> anyhow::Result<Vec<u8>>
+where
+    A: KeyInit + Aead,
+{
+    if key.len() != A::key_size() {
+        return Err(anyhow!("Invalid key length"));
+    }
+    let nonce_size = A::NonceSize::to_usize();
+    if nonce.len() != nonce_size {
+        return Err(anyhow!(
+            "Invalid nonce length {}, expected {} bytes",
+            nonce.len(),
+            nonce_size
+        ));
+    }
+
+    let cipher = A::new(key.into());
+    let payload = Payload { msg: input, aad };
+    match direction {
+        Direction::Seal => cipher
+            .encrypt(nonce.into(), payload)
+            .map_err(|_| anyhow!("Encryption failed")),
+        Direction::Open => {
+            if input.len() < A::TagSize::to_usize() {
+                return Err(anyhow!("Input is shorter than the tag"));
+            }
+            cipher
+                .decrypt(nonce.into(), payload)
+                .map_err(|_| anyhow!("Authentication failed: the tag does not match"))
+        }
+    }
+}
+
+/// Decrypts by regenerating the keystream of the underlying CTR mode, so a
+/// forged tag is simply discarded. GCM-SIV is the exception: its tag doubles
+/// as the initial counter block and has to be intact.
+fn open_unverified(
+    mode: AeadMode,
+    input: &[u8],
+    key: &[u8],
+    nonce: &[u8],
+    tag_length: usize,
+) -> anyhow::Result<Vec<u8>> {
+    check_tag_length(mode, tag_length)?;
+    if input.len() < tag_length {
+        return Err(anyhow!("Input is shorter than the tag"));
+    }
+    let (ct, tag) = input.split_at(input.len() - tag_length);
+    match mode {
+        AeadMode::Gcm => {
+            if nonce.len() != 12 {
+                return Err(anyhow!(
+                    "Invalid nonce length {}, expected 12 bytes",
+                    nonce.len()
+                ));
+            }
+            // J0 = nonce || 1, the payload starts at inc32(J0)
+            let mut iv = nonce.to_vec();
+            iv.extend([0, 0, 0, 2]);
+            aes_ctr(ct, key, &iv, CounterWidth::Bits32, Endianness::Big)
+        }
+        AeadMode::Ccm => {
+            if !(7..=13).contains(&nonce.len()) {
+                return Err(anyhow!(
+                    "Invalid nonce length {}, expected 7 to 13 bytes",
+                    nonce.len()
+                ));
+            }
+            // A_i = flags(L - 1) || nonce || i, the payload starts at A_1
+            let mut iv = vec![(14 - nonce.len()) as u8];
+            iv.extend(nonce);
+            iv.resize(16, 0);
+            iv[15] = 1;
+            aes_ctr(ct, key, &iv, CounterWidth::Bits128, Endianness::Big)
+        }
+        AeadMode::GcmSiv => {
+            if nonce.len() != 12 {
+                return Err(anyhow!(
+                    "Invalid nonce length {}, expected 12 bytes",
+                    nonce.len()
+                ));
+            }
+            let enc_key = gcm_siv_encryption_key(key, nonce)?;
+            let mut iv = tag.to_vec();
+            iv[15] |= 0x80;
+            aes_ctr(ct, &enc_key, &iv, CounterWidth::Bits32, Endianness::Little)
+        }
+        AeadMode::Eax => {
+            // N = OMAC_K(0 || nonce)
+            let iv = with_aes!(key.len(), |C| {
+                let mut mac = <Cmac<C> as Mac>::new_from_slice(key)
+                    .map_err(|_| anyhow!("Invalid key length"))?;
+                mac.update(&[0; 16]);
+                mac.update(nonce);
+                Ok::<_, anyhow::Error>(mac.finalize().into_bytes().to_vec())
+            })?;
+            aes_ctr(ct, key, &iv, CounterWidth::Bits128, Endianness::Big)
+        }
+    }
+}
+
+/// Message-encryption key derivation from RFC 8452 section 4.
+fn gcm_siv_encryption_key(key: &[u8], nonce: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let counters = match key.len() {
+        16 => 2..4,
+        32 => 2..6,
+        n => return Err(anyhow!("Invalid key length {}, expected 16 or 32 bytes", n)),
+    };
+    let mut enc_key = Vec::with_capacity(key.len());
+    for i in counters {
+        let mut block = [0u8; 16];
+        block[..4].copy_from_slice(&(i as u32).to_le_bytes());
+        block[4..].copy_from_slice(nonce);
+        let block = with_aes!(key.len(), |C| {
+            let mut block = block.into();
+            C::new(key.into()).encrypt_block(&mut block);
+            Ok::<_, anyhow::Error>(block)
+        })?;
+        enc_key.extend(&block[..8]);
+    }
+    Ok(enc_key)
+}
@@ -1,11 +1,17 @@
-use aes::cipher::{BlockDecryptMut, BlockEncryptMut, KeyInit, KeyIvInit, Unsigned};
-use anyhow::anyhow;
-use block_padding::{Padding, Pkcs7};
-use eframe::egui::{ComboBox, Grid, Id, Ui};
-use strum::IntoEnumIterator;
+use aes::cipher::{
+    consts::U16, AsyncStreamCipher, BlockCipher, BlockDecryptMut, BlockEncryptMut, BlockSizeUser,
+    KeyInit, KeyIvInit, StreamCipher, Unsigned,
+};
+use anyhow::{anyhow, bail};
+use block_padding::{AnsiX923, Iso10126, Iso7816, NoPadding, PadType, Padding, Pkcs7, ZeroPadding};
 use uuid::Uuid;
 
-use crate::modules::{crypto::EncryptMode, input::Argument, Module};
+use crate::modules::{
+    args::{Arg, Kind, Note},
+    crypto::{CounterWidth, EncryptMode, Endianness, Kdf, KeyBits, KeySize, PaddingMode},
+    input::Argument,
+    register_module, Category, Module,
+};
 
 type Aes128CbcEnc = cbc::Encryptor<aes::Aes128>;
 type Aes128CbcDec = cbc::Decryptor<aes::Aes128>;
@@ -19,28 +25,33 @@ type Aes192EcbEnc = ecb::Encryptor<aes::Aes192>;
 type Aes192EcbDec = ecb::Decryptor<aes::Aes192>;
 type Aes256EcbEnc = ecb::Encryptor<aes::Aes256>;
 type Aes256EcbDec = ecb::Decryptor<aes::Aes256>;
-
+type Aes128CfbEnc = cfb_mode::Encryptor<aes::Aes128>;
+type Aes128CfbDec = cfb_mode::Decryptor<aes::Aes128>;
+type Aes192CfbEnc = cfb_mode::Encryptor<aes::Aes192>;
+type Aes192CfbDec = cfb_mode::Decryptor<aes::Aes192>;
+type Aes256CfbEnc = cfb_mode::Encryptor<aes::Aes256>;
+type Aes256CfbDec = cfb_mode::Decryptor<aes::Aes256>;
+type Aes128Ofb = ofb::Ofb<aes::Aes128>;
+type Aes192Ofb = ofb::Ofb<aes::Aes192>;
+type Aes256Ofb = ofb::Ofb<aes::Aes256>;
+
+#[derive(Clone)]
 pub struct AESEncrypt {
     id: String,
-    mode: EncryptMode,
-    key: Argument,
-    iv: Argument,
+    params: AesParams,
 }
 
+#[derive(Clone)]
 pub struct AESDecrypt {
     id: String,
-    mode: EncryptMode,
-    key: Argument,
-    iv: Argument,
+    params: AesParams,
 }
 
 impl Default for AESDecrypt {
     fn default() -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
-            mode: EncryptMode::Cbc,
-            key: Argument::default(),
-            iv: Argument::default(),
+            params: AesParams::default(),
         }
     }
 }
@@ -49,14 +60,18 @@ impl Default for AESEncrypt {
     fn default() -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
-            mode: EncryptMode::Cbc,
-            key: Argument::default(),
-            iv: Argument::default(),
+            params: AesParams::default(),
         }
     }
 }
 
+register_module!(AESEncrypt, "aes-encrypt");
+
 impl Module for AESEncrypt {
+    fn key(&self) -> &'static str {
+        "aes-encrypt"
+    }
+
     fn name(&self) -> &str {
         "AES Encrypt"
     }
@@ -65,72 +80,82 @@ impl Module for AESEncrypt {
         "Encrypt input with AES"
     }
 
+    fn category(&self) -> Category {
+        Category::Crypto
+    }
+
     fn id(&self) -> &str {
         self.id.as_str()
     }
 
     fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
         // encrypt
-        let key = self.key.try_to_vec()?;
-        let iv = self.iv.try_to_vec()?;
-        match self.mode {
-            EncryptMode::Cbc => {
+        let params = &self.params;
+        let (key, iv, header) = params.encrypt_key_iv()?;
+        let padding = params.mode.padding(params.padding);
+        let ciphertext = match params.mode {
+            EncryptMode::Cbc | EncryptMode::CbcNoPadding => {
                 if key.len() == 16 && iv.len() == 16 {
-                    block_encrypt_with_iv::<Aes128CbcEnc, Pkcs7>(input, &key, &iv)
+                    padded_encrypt_with_iv::<Aes128CbcEnc>(input, &key, &iv, padding)
                 } else if key.len() == 24 && iv.len() == 16 {
-                    block_encrypt_with_iv::<Aes192CbcEnc, Pkcs7>(input, &key, &iv)
+                    padded_encrypt_with_iv::<Aes192CbcEnc>(input, &key, &iv, padding)
                 } else if key.len() == 32 && iv.len() == 16 {
-                    block_encrypt_with_iv::<Aes256CbcEnc, Pkcs7>(input, &key, &iv)
+                    padded_encrypt_with_iv::<Aes256CbcEnc>(input, &key, &iv, padding)
                 } else {
                     Err(anyhow!("Invalid key or iv length"))
                 }
             }
-            EncryptMode::Ecb => {
+            EncryptMode::Ecb | EncryptMode::EcbNoPadding => {
                 if key.len() == 16 {
-                    block_encrypt::<Aes128EcbEnc, Pkcs7>(input, &key)
+                    padded_encrypt::<Aes128EcbEnc>(input, &key, padding)
                 } else if key.len() == 24 {
-                    block_encrypt::<Aes192EcbEnc, Pkcs7>(input, &key)
+                    padded_encrypt::<Aes192EcbEnc>(input, &key, padding)
                 } else if key.len() == 32 {
-                    block_encrypt::<Aes256EcbEnc, Pkcs7>(input, &key)
+                    padded_encrypt::<Aes256EcbEnc>(input, &key, padding)
                 } else {
                     Err(anyhow!("Invalid key or iv length"))
                 }
             }
-            _ => Err(anyhow!("Unsupported mode")),
-        }
+            EncryptMode::Cfb => {
+                if key.len() == 16 {
+                    cfb_encrypt::<Aes128CfbEnc>(input, &key, &iv)
+                } else if key.len() == 24 {
+                    cfb_encrypt::<Aes192CfbEnc>(input, &key, &iv)
+                } else if key.len() == 32 {
+                    cfb_encrypt::<Aes256CfbEnc>(input, &key, &iv)
+                } else {
+                    Err(anyhow!("Invalid key or iv length"))
+                }
+            }
+            EncryptMode::Ofb => aes_ofb(input, &key, &iv),
+            EncryptMode::Ctr => aes_ctr(input, &key, &iv, params.counter, params.endian),
+        }?;
+        Ok([header, ciphertext].concat())
     }
 
-    fn render_inner(&mut self, ui: &mut Ui) {
-        Grid::new(Id::new((&self.id, "grid")))
-            .striped(true)
-            .num_columns(2)
-            .show(ui, |ui| {
-                ui.label("Mode");
-                ComboBox::from_id_salt(Id::new((&self.id, "mode")))
-                    .selected_text(self.mode.to_string())
-                    .show_ui(ui, |ui| {
-                        for mode in EncryptMode::iter() {
-                            ui.selectable_value(&mut self.mode, mode, mode.to_string());
-                        }
-                    });
-                ui.end_row();
-
-                ui.label("Key");
-                self.key.show(ui);
-                ui.end_row();
-
-                ui.label("IV");
-                self.iv.show(ui);
-                ui.end_row();
-            });
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        self.params.args(true)
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        self.params.notes(true)
     }
 
     fn clone_box(&self) -> Box<dyn Module> {
-        Box::new(Self::default())
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
     }
 }
 
+register_module!(AESDecrypt, "aes-decrypt");
+
 impl Module for AESDecrypt {
+    fn key(&self) -> &'static str {
+        "aes-decrypt"
+    }
+
     fn name(&self) -> &str {
         "AES Decrypt"
     }
@@ -139,71 +164,380 @@ impl Module for AESDecrypt {
         "Decrypt input with AES"
     }
 
+    fn category(&self) -> Category {
+        Category::Crypto
+    }
+
     fn id(&self) -> &str {
         self.id.as_str()
     }
 
     fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
         // decrypt
-        let key = self.key.try_to_vec()?;
-        let iv = self.iv.try_to_vec()?;
-        match self.mode {
-            EncryptMode::Cbc => {
+        let params = &self.params;
+        let (key, iv, input) = params.decrypt_key_iv(input)?;
+        let padding = params.mode.padding(params.padding);
+        match params.mode {
+            EncryptMode::Cbc | EncryptMode::CbcNoPadding => {
                 if key.len() == 16 && iv.len() == 16 {
-                    block_decrypt_with_iv::<Aes128CbcDec, Pkcs7>(input, &key, &iv)
+                    padded_decrypt_with_iv::<Aes128CbcDec>(input, &key, &iv, padding)
                 } else if key.len() == 24 && iv.len() == 16 {
-                    block_decrypt_with_iv::<Aes192CbcDec, Pkcs7>(input, &key, &iv)
+                    padded_decrypt_with_iv::<Aes192CbcDec>(input, &key, &iv, padding)
                 } else if key.len() == 32 && iv.len() == 16 {
-                    block_decrypt_with_iv::<Aes256CbcDec, Pkcs7>(input, &key, &iv)
+                    padded_decrypt_with_iv::<Aes256CbcDec>(input, &key, &iv, padding)
+                } else {
+                    Err(anyhow!("Invalid key or iv length"))
+                }
+            }
+            EncryptMode::Ecb | EncryptMode::EcbNoPadding => {
+                if key.len() == 16 {
+                    padded_decrypt::<Aes128EcbDec>(input, &key, padding)
+                } else if key.len() == 24 {
+                    padded_decrypt::<Aes192EcbDec>(input, &key, padding)
+                } else if key.len() == 32 {
+                    padded_decrypt::<Aes256EcbDec>(input, &key, padding)
                 } else {
                     Err(anyhow!("Invalid key or iv length"))
                 }
             }
-            EncryptMode::Ecb => {
+            EncryptMode::Cfb => {
                 if key.len() == 16 {
-                    block_decrypt::<Aes128EcbDec, Pkcs7>(input, &key)
+                    cfb_decrypt::<Aes128CfbDec>(input, &key, &iv)
                 } else if key.len() == 24 {
-                    block_decrypt::<Aes192EcbDec, Pkcs7>(input, &key)
+                    cfb_decrypt::<Aes192CfbDec>(input, &key, &iv)
                 } else if key.len() == 32 {
-                    block_decrypt::<Aes256EcbDec, Pkcs7>(input, &key)
+                    cfb_decrypt::<Aes256CfbDec>(input, &key, &iv)
                 } else {
                     Err(anyhow!("Invalid key or iv length"))
                 }
             }
-            _ => Err(anyhow!("Unsupported mode")),
+            EncryptMode::Ofb => aes_ofb(input, &key, &iv),
+            EncryptMode::Ctr => aes_ctr(input, &key, &iv, params.counter, params.endian),
         }
     }
 
-    fn render_inner(&mut self, ui: &mut Ui) {
-        Grid::new(Id::new((&self.id, "grid")))
-            .striped(true)
-            .num_columns(2)
-            .show(ui, |ui| {
-                ui.label("Mode");
-                ComboBox::from_id_salt(Id::new((&self.id, "mode")))
-                    .selected_text(self.mode.to_string())
-                    .show_ui(ui, |ui| {
-                        for mode in EncryptMode::iter() {
-                            ui.selectable_value(&mut self.mode, mode, mode.to_string());
-                        }
-                    });
-                ui.end_row();
-
-                ui.label("Key");
-                self.key.show(ui);
-                ui.end_row();
-
-                ui.label("IV");
-                self.iv.show(ui);
-                ui.end_row();
-            });
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        self.params.args(false)
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        self.params.notes(false)
     }
 
     fn clone_box(&self) -> Box<dyn Module> {
-        Box::new(Self::default())
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+/// Start of `openssl enc` output, followed by the 8-byte salt.
+const SALTED_MAGIC: &[u8] = b"Salted__";
+
+/// Arguments shared by [`AESEncrypt`] and [`AESDecrypt`].
+#[derive(Clone)]
+struct AesParams {
+    mode: EncryptMode,
+    kdf: Kdf,
+    /// The raw key, or the passphrase when `kdf` derives one.
+    key: Argument,
+    key_size: KeySize,
+    key_bits: KeyBits,
+    iterations: usize,
+    salted: bool,
+    /// Salt for encrypting; decrypting reads it from the header.
+    salt: Argument,
+    iv: Argument,
+    padding: PaddingMode,
+    counter: CounterWidth,
+    endian: Endianness,
+}
+
+impl Default for AesParams {
+    fn default() -> Self {
+        Self {
+            mode: EncryptMode::default(),
+            kdf: Kdf::default(),
+            key: Argument::default(),
+            key_size: KeySize::default(),
+            key_bits: KeyBits::default(),
+            // `openssl enc -pbkdf2` without `-iter`
+            iterations: 10000,
+            salted: true,
+            salt: Argument::default(),
+            iv: Argument::default(),
+            padding: PaddingMode::default(),
+            counter: CounterWidth::default(),
+            endian: Endianness::default(),
+        }
+    }
+}
+
+impl AesParams {
+    fn args(&mut self, encrypt: bool) -> Vec<Arg<'_>> {
+        let padded = matches!(self.mode, EncryptMode::Cbc | EncryptMode::Ecb);
+        let ctr = self.mode == EncryptMode::Ctr;
+        let derived = self.kdf != Kdf::None;
+        let pbkdf2 = self.kdf == Kdf::Pbkdf2;
+        let salt = derived && self.salted && encrypt;
+        vec![
+            Arg::new("mode", "Mode", Kind::Enum(&mut self.mode)),
+            Arg::new("kdf", "Key derivation", Kind::Enum(&mut self.kdf))
+                .hint("Derive the key and IV from a passphrase like `openssl enc -pass`"),
+            Arg::new(
+                "key",
+                if derived { "Passphrase" } else { "Key" },
+                Kind::Bytes(&mut self.key),
+            ),
+            Arg::new("key_size", "Key size", Kind::Enum(&mut self.key_size))
+                .hint("Zero-padding also truncates longer keys")
+                .shown_if(!derived),
+            Arg::new("key_bits", "Key size", Kind::Enum(&mut self.key_bits)).shown_if(derived),
+            Arg::new(
+                "iterations",
+                "Iterations",
+                Kind::Int(&mut self.iterations, 1..=10_000_000),
+            )
+            .hint("`openssl enc -iter`")
+            .shown_if(pbkdf2),
+            Arg::new("salted", "Salted__ header", Kind::Bool(&mut self.salted))
+                .hint("Off for `openssl enc -nosalt`")
+                .shown_if(derived),
+            Arg::new("salt", "Salt", Kind::Bytes(&mut self.salt))
+                .hint("8 bytes, or empty for a new random salt on every bake")
+                .shown_if(salt),
+            Arg::new("iv", "IV", Kind::Bytes(&mut self.iv)).shown_if(!derived),
+            Arg::new("padding", "Padding", Kind::Enum(&mut self.padding)).shown_if(padded),
+            Arg::new("counter", "Counter", Kind::Enum(&mut self.counter)).shown_if(ctr),
+            Arg::new("endian", "Counter endianness", Kind::Enum(&mut self.endian)).shown_if(ctr),
+        ]
+    }
+
+    /// Key and IV lengths and the AES variant they select, or the
+    /// passphrase and salt lengths when deriving them.
+    fn notes(&self, encrypt: bool) -> Vec<Note> {
+        // notes are rebuilt every frame, so files are left for baking
+        let peek = |argument: &Argument, field| match argument.is_file() {
+            true => None,
+            false => Some(argument.try_to_vec(field).map_err(|e| e.to_string())),
+        };
+        let mut notes = Vec::new();
+        if self.kdf != Kdf::None {
+            if let Some(passphrase) = peek(&self.key, "Passphrase") {
+                let passphrase = passphrase.map(|passphrase| format!("{} bytes", passphrase.len()));
+                notes.push(Note::new("key", passphrase));
+            }
+            if let Some(salt) = peek(&self.salt, "Salt") {
+                let salt = salt.and_then(|salt| match salt.len() {
+                    0 if encrypt => Ok("random".to_string()),
+                    8 => Ok("8 bytes".to_string()),
+                    len => Err(format!("{} bytes, OpenSSL uses 8", len)),
+                });
+                notes.push(Note::new("salt", salt));
+            }
+            return notes;
+        }
+        if let Some(key) = peek(&self.key, "Key") {
+            let key = key.and_then(|raw| {
+                let key = self.key_size.apply(raw.clone());
+                let checked = check_key(&key)?;
+                Ok(match self.key_size {
+                    KeySize::Exact => checked,
+                    _ => format!("{} bytes → {}", raw.len(), checked),
+                })
+            });
+            notes.push(Note::new("key", key));
+        }
+        if let Some(iv) = peek(&self.iv, "IV") {
+            notes.push(Note::new("iv", iv.and_then(|iv| check_iv(self.mode, &iv))));
+        }
+        notes
+    }
+
+    /// The key after [`KeySize`] and the IV, checked against `mode`.
+    fn key_iv(&self) -> anyhow::Result<(Vec<u8>, Vec<u8>)> {
+        let key = self.key_size.apply(self.key.try_to_vec("Key")?);
+        let iv = self.iv.try_to_vec("IV")?;
+        check_key(&key).map_err(|e| anyhow!("Key: {}", e))?;
+        check_iv(self.mode, &iv).map_err(|e| anyhow!("IV: {}", e))?;
+        Ok((key, iv))
+    }
+
+    /// Key and IV for encrypting, and the `Salted__` header to write before
+    /// the ciphertext.
+    fn encrypt_key_iv(&self) -> anyhow::Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+        if self.kdf == Kdf::None {
+            let (key, iv) = self.key_iv()?;
+            return Ok((key, iv, Vec::new()));
+        }
+        if !self.salted {
+            let (key, iv) = self.derive(&[])?;
+            return Ok((key, iv, Vec::new()));
+        }
+        let mut salt = self.salt.try_to_vec("Salt")?;
+        if salt.is_empty() {
+            salt = vec![0; 8];
+            getrandom::getrandom(&mut salt).map_err(|e| anyhow!("Salt: {}", e))?;
+        } else if salt.len() != 8 {
+            bail!("Salt: {} bytes, OpenSSL uses 8", salt.len());
+        }
+        let (key, iv) = self.derive(&salt)?;
+        Ok((key, iv, [SALTED_MAGIC, &salt].concat()))
+    }
+
+    /// Key and IV for decrypting `input`, and the ciphertext following any
+    /// `Salted__` header.
+    fn decrypt_key_iv<'i>(&self, input: &'i [u8]) -> anyhow::Result<(Vec<u8>, Vec<u8>, &'i [u8])> {
+        if self.kdf == Kdf::None {
+            let (key, iv) = self.key_iv()?;
+            return Ok((key, iv, input));
+        }
+        if !self.salted {
+            let (key, iv) = self.derive(&[])?;
+            return Ok((key, iv, input));
+        }
+        let rest = input.strip_prefix(SALTED_MAGIC).ok_or_else(|| {
+            anyhow!("Input does not start with a Salted__ header, turn it off for `-nosalt`")
+        })?;
+        if rest.len() < 8 {
+            bail!("Input ends inside the salt of the Salted__ header");
+        }
+        let (salt, ciphertext) = rest.split_at(8);
+        let (key, iv) = self.derive(salt)?;
+        Ok((key, iv, ciphertext))
+    }
+
+    /// Key and IV derived from the passphrase the way `openssl enc` does.
+    /// ECB has no IV, so all derived bytes go to the key.
+    fn derive(&self, salt: &[u8]) -> anyhow::Result<(Vec<u8>, Vec<u8>)> {
+        let passphrase = self.key.try_to_vec("Passphrase")?;
+        let key_len = self.key_bits.len();
+        let iv_len = match self.mode {
+            EncryptMode::Ecb | EncryptMode::EcbNoPadding => 0,
+            _ => 16,
+        };
+        let mut key = self
+            .kdf
+            .derive(&passphrase, salt, self.iterations as u32, key_len + iv_len);
+        let iv = key.split_off(key_len);
+        Ok((key, iv))
     }
 }
 
+fn check_key(key: &[u8]) -> Result<String, String> {
+    match key.len() {
+        len @ (16 | 24 | 32) => Ok(format!("{} bytes, AES-{}", len, len * 8)),
+        len => Err(format!("{} bytes, AES needs 16, 24 or 32", len)),
+    }
+}
+
+fn check_iv(mode: EncryptMode, iv: &[u8]) -> Result<String, String> {
+    match mode {
+        EncryptMode::Ecb | EncryptMode::EcbNoPadding => Ok("not used by ECB".to_string()),
+        _ if iv.len() == 16 => Ok("16 bytes".to_string()),
+        _ => Err(format!("{} bytes, {} needs 16", iv.len(), mode)),
+    }
+}
+
+fn padded_encrypt<E>(input: &[u8], key: &[u8], padding: PaddingMode) -> anyhow::Result<Vec<u8>>
+where
+    E: KeyInit + BlockEncryptMut,
+{
+    match padding {
+        PaddingMode::Pkcs7 => block_encrypt::<E, Pkcs7>(input, key),
+        PaddingMode::Zero => block_encrypt::<E, ZeroPadding>(input, key),
+        PaddingMode::AnsiX923 => block_encrypt::<E, AnsiX923>(input, key),
+        PaddingMode::Iso10126 => block_encrypt::<E, Iso10126>(input, key),
+        PaddingMode::Iso7816 => block_encrypt::<E, Iso7816>(input, key),
+        PaddingMode::None => block_encrypt::<E, NoPadding>(input, key),
+    }
+}
+
+fn padded_decrypt<E>(input: &[u8], key: &[u8], padding: PaddingMode) -> anyhow::Result<Vec<u8>>
+where
+    E: KeyInit + BlockDecryptMut,
+{
+    match padding {
+        PaddingMode::Pkcs7 => block_decrypt::<E, Pkcs7>(input, key),
+        PaddingMode::Zero => block_decrypt::<E, ZeroPadding>(input, key),
+        PaddingMode::AnsiX923 => block_decrypt::<E, AnsiX923>(input, key),
+        PaddingMode::Iso10126 => block_decrypt::<E, Iso10126>(input, key),
+        PaddingMode::Iso7816 => block_decrypt::<E, Iso7816>(input, key),
+        PaddingMode::None => block_decrypt::<E, NoPadding>(input, key),
+    }
+}
+
+fn padded_encrypt_with_iv<E>(
+    input: &[u8],
+    key: &[u8],
+    iv: &[u8],
+    padding: PaddingMode,
+) -> anyhow::Result<Vec<u8>>
+where
+    E: KeyIvInit + BlockEncryptMut,
+{
+    match padding {
+        PaddingMode::Pkcs7 => block_encrypt_with_iv::<E, Pkcs7>(input, key, iv),
+        PaddingMode::Zero => block_encrypt_with_iv::<E, ZeroPadding>(input, key, iv),
+        PaddingMode::AnsiX923 => block_encrypt_with_iv::<E, AnsiX923>(input, key, iv),
+        PaddingMode::Iso10126 => block_encrypt_with_iv::<E, Iso10126>(input, key, iv),
+        PaddingMode::Iso7816 => block_encrypt_with_iv::<E, Iso7816>(input, key, iv),
+        PaddingMode::None => block_encrypt_with_iv::<E, NoPadding>(input, key, iv),
+    }
+}
+
+fn padded_decrypt_with_iv<E>(
+    input: &[u8],
+    key: &[u8],
+    iv: &[u8],
+    padding: PaddingMode,
+) -> anyhow::Result<Vec<u8>>
+where
+    E: KeyIvInit + BlockDecryptMut,
+{
+    match padding {
+        PaddingMode::Pkcs7 => block_decrypt_with_iv::<E, Pkcs7>(input, key, iv),
+        PaddingMode::Zero => block_decrypt_with_iv::<E, ZeroPadding>(input, key, iv),
+        PaddingMode::AnsiX923 => block_decrypt_with_iv::<E, AnsiX923>(input, key, iv),
+        PaddingMode::Iso10126 => block_decrypt_with_iv::<E, Iso10126>(input, key, iv),
+        PaddingMode::Iso7816 => block_decrypt_with_iv::<E, Iso7816>(input, key, iv),
+        PaddingMode::None => block_decrypt_with_iv::<E, NoPadding>(input, key, iv),
+    }
+}
+
+/// Without padding the plaintext has to fill whole blocks already.
+fn check_unpadded_input<E, P>(input: &[u8]) -> anyhow::Result<()>
+where
+    E: BlockSizeUser,
+    P: Padding<E::BlockSize>,
+{
+    let block_size = E::BlockSize::to_usize();
+    if P::TYPE == PadType::NoPadding && !input.len().is_multiple_of(block_size) {
+        return Err(anyhow!(
+            "Input length {} is not a multiple of the block size ({}), select a padding",
+            input.len(),
+            block_size
+        ));
+    }
+    Ok(())
+}
+
+fn check_ciphertext_length<E>(input: &[u8]) -> anyhow::Result<()>
+where
+    E: BlockSizeUser,
+{
+    let block_size = E::BlockSize::to_usize();
+    if !input.len().is_multiple_of(block_size) {
+        return Err(anyhow!(
+            "Ciphertext length {} is not a multiple of the block size ({})",
+            input.len(),
+            block_size
+        ));
+    }
+    Ok(())
+}
+
 fn block_encrypt<E, P>(input: &[u8], key: &[u8]) -> anyhow::Result<Vec<u8>>
 where
     E: KeyInit + BlockEncryptMut,
@@ -212,6 +546,7 @@ where
     if key.len() != E::KeySize::to_usize() {
         return Err(anyhow!("Invalid key length"));
     }
+    check_unpadded_input::<E, P>(input)?;
 
     let ct = E::new(key.into()).encrypt_padded_vec_mut::<P>(input);
 
@@ -226,10 +561,11 @@ where
     if key.len() != E::KeySize::to_usize() {
         return Err(anyhow!("Invalid key length"));
     }
+    check_ciphertext_length::<E>(input)?;
 
     let pt = E::new(key.into())
         .decrypt_padded_vec_mut::<P>(input)
-        .map_err(|e| anyhow!(e))?;
+        .map_err(|_| anyhow!("Invalid padding, the key, iv or padding mode may be wrong"))?;
 
     Ok(pt.to_vec())
 }
@@ -242,6 +578,7 @@ where
     if key.len() != E::KeySize::to_usize() || iv.len() != E::IvSize::to_usize() {
         return Err(anyhow!("Invalid key or iv length"));
     }
+    check_unpadded_input::<E, P>(input)?;
 
     let ct = E::new(key.into(), iv.into()).encrypt_padded_vec_mut::<P>(input);
 
@@ -256,10 +593,117 @@ where
     if key.len() != E::KeySize::to_usize() || iv.len() != E::IvSize::to_usize() {
         return Err(anyhow!("Invalid key or iv length"));
     }
+    check_ciphertext_length::<E>(input)?;
 
     let pt = E::new(key.into(), iv.into())
         .decrypt_padded_vec_mut::<P>(input)
-        .map_err(|e| anyhow!(e))?;
+        .map_err(|_| anyhow!("Invalid padding, the key, iv or padding mode may be wrong"))?;
 
     Ok(pt.to_vec())
 }
+
+fn cfb_encrypt<E>(input: &[u8], key: &[u8], iv: &[u8]) -> anyhow::Result<Vec<u8>>
+where
+    E: KeyIvInit + AsyncStreamCipher + BlockEncryptMut,
+{
+    if key.len() != E::KeySize::to_usize() || iv.len() != E::IvSize::to_usize() {
+        return Err(anyhow!("Invalid key or iv length"));
+    }
+
+    let mut ct = input.to_vec();
+    E::new(key.into(), iv.into()).encrypt(&mut ct);
+
+    Ok(ct)
+}
+
+fn cfb_decrypt<D>(input: &[u8], key: &[u8], iv: &[u8]) -> anyhow::Result<Vec<u8>>
+where
+    D: KeyIvInit + AsyncStreamCipher + BlockDecryptMut,
+{
+    if key.len() != D::KeySize::to_usize() || iv.len() != D::IvSize::to_usize() {
+        return Err(anyhow!("Invalid key or iv length"));
+    }
+
+    let mut pt = input.to_vec();
+    D::new(key.into(), iv.into()).decrypt(&mut pt);
+
+    Ok(pt)
+}
+
+/// Applies the keystream of a synchronous stream mode (OFB, CTR). Encryption
+/// and decryption are the same operation.
+fn stream_apply<C>(input: &[u8], key: &[u8], iv: &[u8]) -> anyhow::Result<Vec<u8>>
+where
+    C: KeyIvInit + StreamCipher,
+{
+    if key.len() != C::KeySize::to_usize() || iv.len() != C::IvSize::to_usize() {
+        return Err(anyhow!("Invalid key or iv length"));
+    }
+
+    let mut buf = input.to_vec();
+    C::new(key.into(), iv.into())
+        .try_apply_keystream(&mut buf)
+        .map_err(|_| anyhow!("Counter overflow, input is too long for this counter width"))?;
+
+    Ok(buf)
+}
+
+fn aes_ofb(input: &[u8], key: &[u8], iv: &[u8]) -> anyhow::Result<Vec<u8>> {
+    if key.len() == 16 {
+        stream_apply::<Aes128Ofb>(input, key, iv)
+    } else if key.len() == 24 {
+        stream_apply::<Aes192Ofb>(input, key, iv)
+    } else if key.len() == 32 {
+        stream_apply::<Aes256Ofb>(input, key, iv)
+    } else {
+        Err(anyhow!("Invalid key or iv length"))
+    }
+}
+
+pub(super) fn aes_ctr(
+    input: &[u8],
+    key: &[u8],
+    iv: &[u8],
+    counter: CounterWidth,
+    endian: Endianness,
+) -> anyhow::Result<Vec<u8>> {
+    if key.len() == 16 {
+        ctr_apply::<aes::Aes128>(input, key, iv, counter, endian)
+    } else if key.len() == 24 {
+        ctr_apply::<aes::Aes192>(input, key, iv, counter, endian)
+    } else if key.len() == 32 {
+        ctr_apply::<aes::Aes256>(input, key, iv, counter, endian)
+    } else {
+        Err(anyhow!("Invalid key or iv length"))
+    }
+}
+
+/// CTR with the counter occupying the last (big endian) or first (little
+/// endian) `counter` bits of the IV block; the rest of the IV is the nonce.
+fn ctr_apply<B>(
+    input: &[u8],
+    key: &[u8],
+    iv: &[u8],
+    counter: CounterWidth,
+    endian: Endianness,
+) -> anyhow::Result<Vec<u8>>
+where
+    B: BlockEncryptMut + BlockCipher + BlockSizeUser<BlockSize = U16> + KeyInit,
+{
+    match (counter, endian) {
+        (CounterWidth::Bits32, Endianness::Big) => stream_apply::<ctr::Ctr32BE<B>>(input, key, iv),
+        (CounterWidth::Bits32, Endianness::Little) => {
+            stream_apply::<ctr::Ctr32LE<B>>(input, key, iv)
+        }
+        (CounterWidth::Bits64, Endianness::Big) => stream_apply::<ctr::Ctr64BE<B>>(input, key, iv),
+        (CounterWidth::Bits64, Endianness::Little) => {
+            stream_apply::<ctr::Ctr64LE<B>>(input, key, iv)
+        }
+        (CounterWidth::Bits128, Endianness::Big) => {
+            stream_apply::<ctr::Ctr128BE<B>>(input, key, iv)
+        }
+        (CounterWidth::Bits128, Endianness::Little) => {
+            stream_apply::<ctr::Ctr128LE<B>>(input, key, iv)
+        }
+    }
+}
@@ -1,8 +1,15 @@
+use md5::{Digest, Md5};
+use pbkdf2::pbkdf2_hmac;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use strum_macros::{Display, EnumIter};
 
+pub mod aead;
 pub mod aes;
 
-#[derive(EnumIter, Display, Default, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[derive(
+    EnumIter, Display, Default, PartialEq, Eq, Ord, PartialOrd, Clone, Copy, Serialize, Deserialize,
+)]
 enum EncryptMode {
     #[default]
     #[strum(to_string = "CBC")]
@@ -21,6 +28,17 @@ enum EncryptMode {
     EcbNoPadding = 6,
 }
 
+impl EncryptMode {
+    /// The padding actually used by this mode; the `NoPadding` variants
+    /// override whatever padding is selected.
+    fn padding(self, selected: PaddingMode) -> PaddingMode {
+        match self {
+            EncryptMode::CbcNoPadding | EncryptMode::EcbNoPadding => PaddingMode::None,
+            _ => selected,
+        }
+    }
+}
+
 pub fn pkcs7_pad(input: &[u8], block_size: usize) -> anyhow::Result<Vec<u8>> {
     let padding = block_size - input.len() % block_size;
     let mut result = input.to_vec();
@@ -35,3 +53,177 @@ pub fn pkcs7_unpad(input: &[u8]) -> anyhow::Result<Vec<u8>> {
     }
     Ok(input[..input.len() - padding].to_vec())
 }
+
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum AeadMode {
+    #[default]
+    #[strum(to_string = "GCM")]
+    Gcm = 0,
+    #[strum(to_string = "CCM")]
+    Ccm = 1,
+    #[strum(to_string = "GCM-SIV")]
+    GcmSiv = 2,
+    #[strum(to_string = "EAX")]
+    Eax = 3,
+}
+
+impl AeadMode {
+    /// Tag lengths, in bytes, the mode can be instantiated with.
+    fn tag_lengths(self) -> &'static [usize] {
+        match self {
+            AeadMode::Gcm => &[12, 13, 14, 15, 16],
+            AeadMode::Ccm => &[4, 6, 8, 10, 12, 14, 16],
+            AeadMode::GcmSiv => &[16],
+            AeadMode::Eax => &[4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
+        }
+    }
+}
+
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum PaddingMode {
+    #[default]
+    #[strum(to_string = "PKCS#7")]
+    Pkcs7 = 0,
+    #[strum(to_string = "Zero")]
+    Zero = 1,
+    #[strum(to_string = "ANSI X.923")]
+    AnsiX923 = 2,
+    #[strum(to_string = "ISO 10126")]
+    Iso10126 = 3,
+    #[strum(to_string = "ISO/IEC 7816-4")]
+    Iso7816 = 4,
+    #[strum(to_string = "None")]
+    None = 5,
+}
+
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum CounterWidth {
+    #[strum(to_string = "32 bit")]
+    Bits32 = 0,
+    #[strum(to_string = "64 bit")]
+    Bits64 = 1,
+    #[default]
+    #[strum(to_string = "128 bit")]
+    Bits128 = 2,
+}
+
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum Endianness {
+    #[default]
+    #[strum(to_string = "Big Endian")]
+    Big = 0,
+    #[strum(to_string = "Little Endian")]
+    Little = 1,
+}
+
+/// How the key argument is turned into an AES key.
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum KeySize {
+    /// The key must already be 16, 24 or 32 bytes long.
+    #[default]
+    #[strum(to_string = "As given")]
+    Exact = 0,
+    #[strum(to_string = "Zero-pad to 128 bit")]
+    Pad128 = 1,
+    #[strum(to_string = "Zero-pad to 192 bit")]
+    Pad192 = 2,
+    #[strum(to_string = "Zero-pad to 256 bit")]
+    Pad256 = 3,
+    #[strum(to_string = "MD5 (128 bit)")]
+    Md5 = 4,
+    #[strum(to_string = "SHA-256 (256 bit)")]
+    Sha256 = 5,
+}
+
+impl KeySize {
+    /// Zero-pads or truncates the key, or hashes it, as selected.
+    fn apply(self, key: Vec<u8>) -> Vec<u8> {
+        let fit = |mut key: Vec<u8>, len| {
+            key.resize(len, 0);
+            key
+        };
+        match self {
+            KeySize::Exact => key,
+            KeySize::Pad128 => fit(key, 16),
+            KeySize::Pad192 => fit(key, 24),
+            KeySize::Pad256 => fit(key, 32),
+            KeySize::Md5 => Md5::digest(key).to_vec(),
+            KeySize::Sha256 => Sha256::digest(key).to_vec(),
+        }
+    }
+}
+
+/// How `openssl enc` turns a passphrase into the key and IV.
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum Kdf {
+    /// The key and IV arguments are used as they are.
+    #[default]
+    #[strum(to_string = "None (raw key and IV)")]
+    None = 0,
+    /// `openssl enc -md md5`, the default before OpenSSL 1.1.0.
+    #[strum(to_string = "EVP_BytesToKey MD5")]
+    BytesToKeyMd5 = 1,
+    /// `openssl enc`, the default since OpenSSL 1.1.0.
+    #[strum(to_string = "EVP_BytesToKey SHA-256")]
+    BytesToKeySha256 = 2,
+    /// `openssl enc -pbkdf2 -iter N`.
+    #[strum(to_string = "PBKDF2 SHA-256")]
+    Pbkdf2 = 3,
+}
+
+impl Kdf {
+    /// `len` bytes of key material followed by the IV, as OpenSSL derives
+    /// them. `iterations` only applies to PBKDF2.
+    fn derive(self, passphrase: &[u8], salt: &[u8], iterations: u32, len: usize) -> Vec<u8> {
+        match self {
+            Kdf::None => passphrase.to_vec(),
+            Kdf::BytesToKeyMd5 => bytes_to_key::<Md5>(passphrase, salt, len),
+            Kdf::BytesToKeySha256 => bytes_to_key::<Sha256>(passphrase, salt, len),
+            Kdf::Pbkdf2 => {
+                let mut derived = vec![0; len];
+                pbkdf2_hmac::<Sha256>(passphrase, salt, iterations, &mut derived);
+                derived
+            }
+        }
+    }
+}
+
+/// `EVP_BytesToKey` with an iteration count of 1, as used by `openssl enc`:
+/// `D_i = H(D_(i-1) || passphrase || salt)`.
+fn bytes_to_key<D: Digest>(passphrase: &[u8], salt: &[u8], len: usize) -> Vec<u8> {
+    let mut derived = Vec::with_capacity(len);
+    let mut block = Vec::new();
+    while derived.len() < len {
+        block = D::new()
+            .chain_update(&block)
+            .chain_update(passphrase)
+            .chain_update(salt)
+            .finalize()
+            .to_vec();
+        derived.extend_from_slice(&block);
+    }
+    derived.truncate(len);
+    derived
+}
+
+/// AES variant of a key derived from a passphrase.
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum KeyBits {
+    #[strum(to_string = "AES-128")]
+    Aes128 = 0,
+    #[strum(to_string = "AES-192")]
+    Aes192 = 1,
+    #[default]
+    #[strum(to_string = "AES-256")]
+    Aes256 = 2,
+}
+
+impl KeyBits {
+    fn len(self) -> usize {
+        match self {
+            KeyBits::Aes128 => 16,
+            KeyBits::Aes192 => 24,
+            KeyBits::Aes256 => 32,
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+use uuid::Uuid;
+
+use crate::modules::{
+    args::{Arg, Kind},
+    encoding::invalid_symbol,
+    register_module, Category, Module,
+};
+
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum Variant {
+    #[default]
+    #[strum(to_string = "RFC 4648")]
+    Standard = 0,
+    /// RFC 4648 "base32hex", which keeps the sort order of the input.
+    #[strum(to_string = "RFC 4648 hex")]
+    Hex = 1,
+    /// Douglas Crockford's alphabet without I, L, O and U. Never padded.
+    #[strum(to_string = "Crockford")]
+    Crockford = 2,
+}
+
+impl Variant {
+    fn alphabet(self) -> &'static [u8; 32] {
+        match self {
+            Variant::Standard => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+            Variant::Hex => b"0123456789ABCDEFGHIJKLMNOPQRSTUV",
+            Variant::Crockford => b"0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+        }
+    }
+
+    /// Value of `symbol`, ignoring case. Crockford also reads O as 0 and
+    /// I and L as 1.
+    fn value(self, symbol: u8) -> Option<u8> {
+        let symbol = symbol.to_ascii_uppercase();
+        let symbol = match (self, symbol) {
+            (Variant::Crockford, b'O') => b'0',
+            (Variant::Crockford, b'I' | b'L') => b'1',
+            _ => symbol,
+        };
+        let position = self.alphabet().iter().position(|&c| c == symbol)?;
+        Some(position as u8)
+    }
+}
+
+#[derive(Clone)]
+pub struct Base32Encoder {
+    id: String,
+    variant: Variant,
+    padding: bool,
+}
+
+#[derive(Clone)]
+pub struct Base32Decoder {
+    id: String,
+    variant: Variant,
+}
+
+impl Default for Base32Encoder {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            variant: Variant::default(),
+            padding: true,
+        }
+    }
+}
+
+impl Default for Base32Decoder {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            variant: Variant::default(),
+        }
+    }
+}
+
+register_module!(Base32Encoder, "base32-encode");
+
+impl Module for Base32Encoder {
+    fn key(&self) -> &'static str {
+        "base32-encode"
+    }
+
+    fn name(&self) -> &str {
+        "Base32 Encoder"
+    }
+
+    fn description(&self) -> &str {
+        "Encode input to Base32"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let alphabet = self.variant.alphabet();
+        let mut encoded = Vec::with_capacity(input.len().div_ceil(5) * 8);
+        let mut buffer = 0u16;
+        let mut bits = 0;
+        for &byte in input {
+            buffer = buffer << 8 | byte as u16;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                encoded.push(alphabet[(buffer >> bits) as usize & 31]);
+            }
+        }
+        if bits > 0 {
+            encoded.push(alphabet[(buffer << (5 - bits)) as usize & 31]);
+        }
+        if self.padding && self.variant != Variant::Crockford {
+            while encoded.len() % 8 != 0 {
+                encoded.push(b'=');
+            }
+        }
+        Ok(encoded)
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        let crockford = self.variant == Variant::Crockford;
+        vec![
+            Arg::new("variant", "Alphabet", Kind::Enum(&mut self.variant)),
+            Arg::new("padding", "Padding", Kind::Bool(&mut self.padding)).shown_if(!crockford),
+        ]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+register_module!(Base32Decoder, "base32-decode");
+
+impl Module for Base32Decoder {
+    fn key(&self) -> &'static str {
+        "base32-decode"
+    }
+
+    fn name(&self) -> &str {
+        "Base32 Decoder"
+    }
+
+    fn description(&self) -> &str {
+        "Decode input from Base32"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        // case-insensitive, skipping whitespace, padding and Crockford's
+        // `-` separators
+        let mut decoded = Vec::with_capacity(input.len() * 5 / 8);
+        let mut buffer = 0u16;
+        let mut bits = 0;
+        for (position, &symbol) in input.iter().enumerate() {
+            let skipped = symbol.is_ascii_whitespace()
+                || symbol == b'='
+                || (symbol == b'-' && self.variant == Variant::Crockford);
+            if skipped {
+                continue;
+            }
+            let value = self
+                .variant
+                .value(symbol)
+                .ok_or_else(|| invalid_symbol("Base32", symbol, position))?;
+            buffer = buffer << 5 | value as u16;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                decoded.push((buffer >> bits) as u8);
+            }
+        }
+        Ok(decoded)
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        vec![Arg::new(
+            "variant",
+            "Alphabet",
+            Kind::Enum(&mut self.variant),
+        )]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
@@ -0,0 +1,142 @@
+use anyhow::bail;
+use uuid::Uuid;
+
+use crate::modules::{encoding::invalid_symbol, register_module, Category, Module};
+
+/// RFC 9285 alphabet. The space is a symbol, so no whitespace is skipped.
+const ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// Base45 as used in QR codes (RFC 9285): every two bytes become three
+/// characters, least significant first.
+pub struct Base45Encoder {
+    id: String,
+}
+
+pub struct Base45Decoder {
+    id: String,
+}
+
+impl Default for Base45Encoder {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+        }
+    }
+}
+
+impl Default for Base45Decoder {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+        }
+    }
+}
+
+register_module!(Base45Encoder, "base45-encode");
+
+impl Module for Base45Encoder {
+    fn key(&self) -> &'static str {
+        "base45-encode"
+    }
+
+    fn name(&self) -> &str {
+        "Base45 Encoder"
+    }
+
+    fn description(&self) -> &str {
+        "Encode input to Base45"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut encoded = Vec::with_capacity(input.len().div_ceil(2) * 3);
+        for pair in input.chunks(2) {
+            let (mut value, digits) = match *pair {
+                [high, low] => ((high as usize) << 8 | low as usize, 3),
+                [byte] => (byte as usize, 2),
+                _ => unreachable!(),
+            };
+            for _ in 0..digits {
+                encoded.push(ALPHABET[value % 45]);
+                value /= 45;
+            }
+        }
+        Ok(encoded)
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self::default())
+    }
+}
+
+register_module!(Base45Decoder, "base45-decode");
+
+impl Module for Base45Decoder {
+    fn key(&self) -> &'static str {
+        "base45-decode"
+    }
+
+    fn name(&self) -> &str {
+        "Base45 Decoder"
+    }
+
+    fn description(&self) -> &str {
+        "Decode input from Base45"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        // a trailing line break is not part of the data, but a space is
+        let end = input
+            .iter()
+            .rposition(|&byte| byte != b'\n' && byte != b'\r')
+            .map_or(0, |last| last + 1);
+        let input = &input[..end];
+        let mut decoded = Vec::with_capacity(input.len() / 3 * 2 + 1);
+        for (index, group) in input.chunks(3).enumerate() {
+            let start = index * 3;
+            if group.len() == 1 {
+                bail!("Invalid Base45: a single character at position {}", start);
+            }
+            let mut value = 0;
+            for (offset, &symbol) in group.iter().enumerate().rev() {
+                let digit = ALPHABET
+                    .iter()
+                    .position(|&c| c == symbol)
+                    .ok_or_else(|| invalid_symbol("Base45", symbol, start + offset))?;
+                value = value * 45 + digit;
+            }
+            let max = if group.len() == 3 { 0xffff } else { 0xff };
+            if value > max {
+                bail!(
+                    "Invalid Base45: \"{}\" at position {} is out of range",
+                    String::from_utf8_lossy(group),
+                    start
+                );
+            }
+            if group.len() == 3 {
+                decoded.push((value >> 8) as u8);
+            }
+            decoded.push(value as u8);
+        }
+        Ok(decoded)
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self::default())
+    }
+}
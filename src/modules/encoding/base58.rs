@@ -0,0 +1,185 @@
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use strum_macros::{Display, EnumIter};
+use uuid::Uuid;
+
+use crate::modules::{
+    args::{Arg, Kind},
+    encoding::{decode_radix, encode_radix},
+    register_module, Category, Module,
+};
+
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum Alphabet {
+    #[default]
+    #[strum(to_string = "Bitcoin")]
+    Bitcoin = 0,
+    #[strum(to_string = "Flickr")]
+    Flickr = 1,
+    #[strum(to_string = "Ripple")]
+    Ripple = 2,
+}
+
+impl Alphabet {
+    fn symbols(self) -> &'static [u8; 58] {
+        match self {
+            Alphabet::Bitcoin => b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+            Alphabet::Flickr => b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ",
+            Alphabet::Ripple => b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz",
+        }
+    }
+}
+
+/// First four bytes of the double SHA-256 of `payload`, as appended by
+/// Base58Check.
+fn checksum(payload: &[u8]) -> [u8; 4] {
+    let hash = Sha256::digest(Sha256::digest(payload));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+#[derive(Clone)]
+pub struct Base58Encoder {
+    id: String,
+    alphabet: Alphabet,
+    /// Append a Base58Check checksum.
+    check: bool,
+}
+
+#[derive(Clone)]
+pub struct Base58Decoder {
+    id: String,
+    alphabet: Alphabet,
+    /// Verify and remove a Base58Check checksum.
+    check: bool,
+}
+
+impl Default for Base58Encoder {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            alphabet: Alphabet::default(),
+            check: false,
+        }
+    }
+}
+
+impl Default for Base58Decoder {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            alphabet: Alphabet::default(),
+            check: false,
+        }
+    }
+}
+
+register_module!(Base58Encoder, "base58-encode");
+
+impl Module for Base58Encoder {
+    fn key(&self) -> &'static str {
+        "base58-encode"
+    }
+
+    fn name(&self) -> &str {
+        "Base58 Encoder"
+    }
+
+    fn description(&self) -> &str {
+        "Encode input to Base58"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let symbols = self.alphabet.symbols();
+        if !self.check {
+            return Ok(encode_radix(input, symbols));
+        }
+        let payload = [input, &checksum(input)].concat();
+        Ok(encode_radix(&payload, symbols))
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        vec![
+            Arg::new("alphabet", "Alphabet", Kind::Enum(&mut self.alphabet)),
+            Arg::new("check", "Base58Check", Kind::Bool(&mut self.check))
+                .hint("Append a 4-byte double SHA-256 checksum"),
+        ]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+register_module!(Base58Decoder, "base58-decode");
+
+impl Module for Base58Decoder {
+    fn key(&self) -> &'static str {
+        "base58-decode"
+    }
+
+    fn name(&self) -> &str {
+        "Base58 Decoder"
+    }
+
+    fn description(&self) -> &str {
+        "Decode input from Base58"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut decoded = decode_radix(input, self.alphabet.symbols(), "Base58")?;
+        if !self.check {
+            return Ok(decoded);
+        }
+        if decoded.len() < 4 {
+            bail!(
+                "Base58Check: {} bytes is too short for the checksum",
+                decoded.len()
+            );
+        }
+        let found = decoded.split_off(decoded.len() - 4);
+        let expected = checksum(&decoded);
+        if found != expected {
+            bail!(
+                "Base58Check: checksum {} does not match {}",
+                hex::encode(found),
+                hex::encode(expected)
+            );
+        }
+        Ok(decoded)
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        vec![
+            Arg::new("alphabet", "Alphabet", Kind::Enum(&mut self.alphabet)),
+            Arg::new("check", "Base58Check", Kind::Bool(&mut self.check))
+                .hint("Verify and remove the 4-byte double SHA-256 checksum"),
+        ]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+use uuid::Uuid;
+
+use crate::modules::{
+    args::{Arg, Kind},
+    encoding::{decode_radix, encode_radix},
+    register_module, Category, Module,
+};
+
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum Alphabet {
+    #[default]
+    #[strum(to_string = "Standard (0-9A-Za-z)")]
+    Standard = 0,
+    #[strum(to_string = "Inverted (0-9a-zA-Z)")]
+    Inverted = 1,
+}
+
+impl Alphabet {
+    fn symbols(self) -> &'static [u8; 62] {
+        match self {
+            Alphabet::Standard => b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+            Alphabet::Inverted => b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        }
+    }
+}
+
+/// The input as one big number, like Base58: leading zero bytes are kept
+/// as leading `0` digits.
+#[derive(Clone)]
+pub struct Base62Encoder {
+    id: String,
+    alphabet: Alphabet,
+}
+
+#[derive(Clone)]
+pub struct Base62Decoder {
+    id: String,
+    alphabet: Alphabet,
+}
+
+impl Default for Base62Encoder {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            alphabet: Alphabet::default(),
+        }
+    }
+}
+
+impl Default for Base62Decoder {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            alphabet: Alphabet::default(),
+        }
+    }
+}
+
+register_module!(Base62Encoder, "base62-encode");
+
+impl Module for Base62Encoder {
+    fn key(&self) -> &'static str {
+        "base62-encode"
+    }
+
+    fn name(&self) -> &str {
+        "Base62 Encoder"
+    }
+
+    fn description(&self) -> &str {
+        "Encode input to Base62"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        Ok(encode_radix(input, self.alphabet.symbols()))
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        vec![Arg::new(
+            "alphabet",
+            "Alphabet",
+            Kind::Enum(&mut self.alphabet),
+        )]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+register_module!(Base62Decoder, "base62-decode");
+
+impl Module for Base62Decoder {
+    fn key(&self) -> &'static str {
+        "base62-decode"
+    }
+
+    fn name(&self) -> &str {
+        "Base62 Decoder"
+    }
+
+    fn description(&self) -> &str {
+        "Decode input from Base62"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        decode_radix(input, self.alphabet.symbols(), "Base62")
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        vec![Arg::new(
+            "alphabet",
+            "Alphabet",
+            Kind::Enum(&mut self.alphabet),
+        )]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
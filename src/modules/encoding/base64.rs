@@ -1,20 +1,108 @@
-use base64::Engine;
+use anyhow::anyhow;
+use base64::{
+    alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine,
+};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
 use uuid::Uuid;
 
-use crate::modules::Module;
+use crate::modules::{
+    args::{Arg, Kind, Note},
+    register_module, Category, Module,
+};
 
+/// Line length of MIME Base64, RFC 2045 section 6.8.
+const MIME_LINE_LENGTH: usize = 76;
+
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum Alphabet {
+    #[default]
+    #[strum(to_string = "Standard (+/)")]
+    Standard = 0,
+    #[strum(to_string = "URL-safe (-_)")]
+    UrlSafe = 1,
+    /// 64 user-supplied characters, e.g. a shuffled alphabet.
+    #[strum(to_string = "Custom")]
+    Custom = 2,
+}
+
+/// The alphabet arguments shared by [`Base64Encoder`] and [`Base64Decoder`].
+#[derive(Clone, Default)]
+struct AlphabetParams {
+    alphabet: Alphabet,
+    custom: String,
+}
+
+impl AlphabetParams {
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        let custom = self.alphabet == Alphabet::Custom;
+        vec![
+            Arg::new("alphabet", "Alphabet", Kind::Enum(&mut self.alphabet)),
+            Arg::new("custom", "Characters", Kind::Text(&mut self.custom, false))
+                .hint("64 distinct printable ASCII characters, without '='")
+                .shown_if(custom),
+        ]
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        if self.alphabet != Alphabet::Custom {
+            return Vec::new();
+        }
+        let checked = self
+            .build()
+            .map(|_| "64 characters".to_string())
+            .map_err(|e| e.to_string());
+        vec![Note::new("custom", checked)]
+    }
+
+    fn build(&self) -> anyhow::Result<alphabet::Alphabet> {
+        Ok(match self.alphabet {
+            Alphabet::Standard => alphabet::STANDARD,
+            Alphabet::UrlSafe => alphabet::URL_SAFE,
+            Alphabet::Custom => alphabet::Alphabet::new(&self.custom).map_err(|e| {
+                anyhow!(
+                    "Characters: {} ({} characters given)",
+                    e,
+                    self.custom.chars().count()
+                )
+            })?,
+        })
+    }
+
+    fn engine(&self, config: GeneralPurposeConfig) -> anyhow::Result<GeneralPurpose> {
+        Ok(GeneralPurpose::new(&self.build()?, config))
+    }
+}
+
+#[derive(Clone)]
 pub struct Base64Encoder {
     id: String,
+    alphabet: AlphabetParams,
+    padding: bool,
+    /// Break lines as MIME does.
+    wrap: bool,
 }
 
+#[derive(Clone)]
 pub struct Base64Decoder {
     id: String,
+    alphabet: AlphabetParams,
+    /// Expect `=` padding, as written by the encoder with the same option.
+    padding: bool,
+    /// Skip whitespace and other characters outside the alphabet, and
+    /// accept missing or partial padding.
+    lenient: bool,
 }
 
 impl Default for Base64Decoder {
     fn default() -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
+            alphabet: AlphabetParams::default(),
+            padding: true,
+            lenient: false,
         }
     }
 }
@@ -23,11 +111,20 @@ impl Default for Base64Encoder {
     fn default() -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
+            alphabet: AlphabetParams::default(),
+            padding: true,
+            wrap: false,
         }
     }
 }
 
+register_module!(Base64Encoder, "base64-encode");
+
 impl Module for Base64Encoder {
+    fn key(&self) -> &'static str {
+        "base64-encode"
+    }
+
     fn name(&self) -> &str {
         "Base64 Encoder"
     }
@@ -36,21 +133,57 @@ impl Module for Base64Encoder {
         "Encode input to Base64"
     }
 
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
     fn id(&self) -> &str {
         self.id.as_str()
     }
     fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
         // encode
-        Ok(Vec::from(
-            base64::engine::general_purpose::STANDARD.encode(input),
-        ))
+        let config = GeneralPurposeConfig::new().with_encode_padding(self.padding);
+        let encoded = self.alphabet.engine(config)?.encode(input);
+        if !self.wrap {
+            return Ok(encoded.into_bytes());
+        }
+        let lines: Vec<_> = encoded.as_bytes().chunks(MIME_LINE_LENGTH).collect();
+        Ok(lines.join(&b"\r\n"[..]))
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        let mut args = self.alphabet.args();
+        args.push(Arg::new(
+            "padding",
+            "Padding",
+            Kind::Bool(&mut self.padding),
+        ));
+        args.push(
+            Arg::new("wrap", "MIME line breaks", Kind::Bool(&mut self.wrap))
+                .hint("Break lines after 76 characters with CRLF, as in RFC 2045"),
+        );
+        args
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        self.alphabet.notes()
     }
+
     fn clone_box(&self) -> Box<dyn Module> {
-        Box::new(Self::default())
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
     }
 }
 
+register_module!(Base64Decoder, "base64-decode");
+
 impl Module for Base64Decoder {
+    fn key(&self) -> &'static str {
+        "base64-decode"
+    }
+
     fn name(&self) -> &str {
         "Base64 Decoder"
     }
@@ -59,16 +192,68 @@ impl Module for Base64Decoder {
         "Decode input from Base64"
     }
 
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
     fn id(&self) -> &str {
         self.id.as_str()
     }
     fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
-        Ok(base64::engine::general_purpose::STANDARD
-            .decode(input)?
-            .to_vec())
+        let padding = match (self.lenient, self.padding) {
+            (true, _) => DecodePaddingMode::Indifferent,
+            (false, true) => DecodePaddingMode::RequireCanonical,
+            (false, false) => DecodePaddingMode::RequireNone,
+        };
+        let config = GeneralPurposeConfig::new()
+            .with_decode_padding_mode(padding)
+            .with_decode_allow_trailing_bits(self.lenient);
+        let engine = self.alphabet.engine(config)?;
+        if !self.lenient {
+            return engine
+                .decode(input)
+                .map_err(|e| anyhow!("Invalid Base64: {}", e));
+        }
+        // whitespace, MIME line breaks, padding and any other noise
+        let alphabet = self.alphabet.build()?;
+        let symbols = alphabet.as_str().as_bytes();
+        let mut filtered: Vec<u8> = input
+            .iter()
+            .copied()
+            .filter(|byte| symbols.contains(byte))
+            .collect();
+        // a single character left over encodes no complete byte
+        if filtered.len() % 4 == 1 {
+            filtered.pop();
+        }
+        engine
+            .decode(filtered)
+            .map_err(|e| anyhow!("Invalid Base64: {}", e))
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        let strict = !self.lenient;
+        let mut args = self.alphabet.args();
+        args.push(
+            Arg::new("padding", "Padding", Kind::Bool(&mut self.padding))
+                .hint("Require '=' padding, or reject it when off")
+                .shown_if(strict),
+        );
+        args.push(
+            Arg::new("lenient", "Lenient", Kind::Bool(&mut self.lenient))
+                .hint("Ignore whitespace, padding and all other characters outside the alphabet"),
+        );
+        args
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        self.alphabet.notes()
     }
 
     fn clone_box(&self) -> Box<dyn Module> {
-        Box::new(Self::default())
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
     }
 }
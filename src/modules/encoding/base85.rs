@@ -0,0 +1,272 @@
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+use uuid::Uuid;
+
+use crate::modules::{
+    args::{Arg, Kind},
+    encoding::invalid_symbol,
+    register_module, Category, Module,
+};
+
+/// All variants turn four bytes into five characters. A final partial
+/// group of n bytes becomes n + 1 characters, except in Z85, which only
+/// encodes whole groups.
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum Variant {
+    /// Adobe/btoa, with `z` for four zero bytes.
+    #[default]
+    #[strum(to_string = "Ascii85")]
+    Ascii85 = 0,
+    /// ZeroMQ's Z85, safe in source code and XML.
+    #[strum(to_string = "Z85")]
+    Z85 = 1,
+    /// git binary patches and Python's `b85encode`: the alphabet of
+    /// RFC 1924, which itself encodes a whole IPv6 address as one number.
+    #[strum(to_string = "Base85 (git / Python b85)")]
+    Git = 2,
+}
+
+impl Variant {
+    fn alphabet(self) -> &'static [u8; 85] {
+        match self {
+            Variant::Ascii85 => {
+                b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu"
+            }
+            Variant::Z85 => {
+                b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#"
+            }
+            Variant::Git => {
+                b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~"
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Base85Encoder {
+    id: String,
+    variant: Variant,
+    /// Wrap Ascii85 in `<~` and `~>` as Adobe does.
+    delimiters: bool,
+}
+
+#[derive(Clone)]
+pub struct Base85Decoder {
+    id: String,
+    variant: Variant,
+}
+
+impl Default for Base85Encoder {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            variant: Variant::default(),
+            delimiters: false,
+        }
+    }
+}
+
+impl Default for Base85Decoder {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            variant: Variant::default(),
+        }
+    }
+}
+
+register_module!(Base85Encoder, "base85-encode");
+
+impl Module for Base85Encoder {
+    fn key(&self) -> &'static str {
+        "base85-encode"
+    }
+
+    fn name(&self) -> &str {
+        "Base85 Encoder"
+    }
+
+    fn description(&self) -> &str {
+        "Encode input to Ascii85, Z85 or git-style Base85"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let alphabet = self.variant.alphabet();
+        let ascii85 = self.variant == Variant::Ascii85;
+        if self.variant == Variant::Z85 && !input.len().is_multiple_of(4) {
+            bail!(
+                "Z85 encodes whole groups of 4 bytes, the input is {} bytes long",
+                input.len()
+            );
+        }
+        let mut encoded = Vec::with_capacity(input.len().div_ceil(4) * 5 + 4);
+        if ascii85 && self.delimiters {
+            encoded.extend_from_slice(b"<~");
+        }
+        for chunk in input.chunks(4) {
+            let mut group = [0; 4];
+            group[..chunk.len()].copy_from_slice(chunk);
+            let mut value = u32::from_be_bytes(group);
+            if ascii85 && chunk.len() == 4 && value == 0 {
+                encoded.push(b'z');
+                continue;
+            }
+            let mut digits = [0; 5];
+            for digit in digits.iter_mut().rev() {
+                *digit = alphabet[(value % 85) as usize];
+                value /= 85;
+            }
+            encoded.extend_from_slice(&digits[..chunk.len() + 1]);
+        }
+        if ascii85 && self.delimiters {
+            encoded.extend_from_slice(b"~>");
+        }
+        Ok(encoded)
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        let ascii85 = self.variant == Variant::Ascii85;
+        vec![
+            Arg::new("variant", "Variant", Kind::Enum(&mut self.variant)),
+            Arg::new(
+                "delimiters",
+                "<~ ~> delimiters",
+                Kind::Bool(&mut self.delimiters),
+            )
+            .shown_if(ascii85),
+        ]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+register_module!(Base85Decoder, "base85-decode");
+
+impl Module for Base85Decoder {
+    fn key(&self) -> &'static str {
+        "base85-decode"
+    }
+
+    fn name(&self) -> &str {
+        "Base85 Decoder"
+    }
+
+    fn description(&self) -> &str {
+        "Decode input from Ascii85, Z85 or git-style Base85"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let alphabet = self.variant.alphabet();
+        let ascii85 = self.variant == Variant::Ascii85;
+        // positions in errors refer to `input`, so track where `body` starts
+        let mut body = input.trim_ascii();
+        let mut offset = input.len() - input.trim_ascii_start().len();
+        if ascii85 {
+            if let Some(rest) = body.strip_prefix(b"<~") {
+                body = rest;
+                offset += 2;
+            }
+            if let Some(rest) = body.strip_suffix(b"~>") {
+                body = rest;
+            }
+        }
+        let mut decoded = Vec::with_capacity(body.len() / 5 * 4 + 4);
+        let mut group = Vec::with_capacity(5);
+        let mut group_start = 0;
+        for (index, &symbol) in body.iter().enumerate() {
+            let position = offset + index;
+            if symbol.is_ascii_whitespace() {
+                continue;
+            }
+            if ascii85 && symbol == b'z' {
+                if !group.is_empty() {
+                    bail!(
+                        "Invalid Base85: 'z' at position {} is inside a group",
+                        position
+                    );
+                }
+                decoded.extend_from_slice(&[0; 4]);
+                continue;
+            }
+            let digit = alphabet
+                .iter()
+                .position(|&c| c == symbol)
+                .ok_or_else(|| invalid_symbol("Base85", symbol, position))?;
+            if group.is_empty() {
+                group_start = position;
+            }
+            group.push(digit as u64);
+            if group.len() == 5 {
+                decoded.extend_from_slice(&decode_group(&group, group_start)?);
+                group.clear();
+            }
+        }
+        match group.len() {
+            0 => {}
+            len if self.variant == Variant::Z85 => bail!(
+                "Invalid Base85: Z85 needs whole groups of 5 characters, the group at position {} has {}",
+                group_start,
+                len
+            ),
+            1 => bail!(
+                "Invalid Base85: a single character at position {} ends the input",
+                group_start
+            ),
+            len => {
+                // pad with the highest digit so the truncated bytes round up
+                group.resize(5, 84);
+                decoded.extend_from_slice(&decode_group(&group, group_start)?[..len - 1]);
+            }
+        }
+        Ok(decoded)
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        vec![Arg::new(
+            "variant",
+            "Variant",
+            Kind::Enum(&mut self.variant),
+        )]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+/// Five digits to four bytes.
+fn decode_group(digits: &[u64], position: usize) -> anyhow::Result<[u8; 4]> {
+    let value = digits.iter().fold(0, |value, &digit| value * 85 + digit);
+    match u32::try_from(value) {
+        Ok(value) => Ok(value.to_be_bytes()),
+        Err(_) => bail!(
+            "Invalid Base85: the group at position {} is out of range",
+            position
+        ),
+    }
+}
@@ -0,0 +1,152 @@
+use uuid::Uuid;
+
+use crate::modules::{encoding::invalid_symbol, register_module, Category, Module};
+
+const ALPHABET: &[u8; 91] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&()*+,./:;<=>?@[]^_`{|}~\"";
+
+/// Joachim Henke's basE91, which packs 13 or 14 bits into two characters.
+pub struct Base91Encoder {
+    id: String,
+}
+
+pub struct Base91Decoder {
+    id: String,
+}
+
+impl Default for Base91Encoder {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+        }
+    }
+}
+
+impl Default for Base91Decoder {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+        }
+    }
+}
+
+register_module!(Base91Encoder, "base91-encode");
+
+impl Module for Base91Encoder {
+    fn key(&self) -> &'static str {
+        "base91-encode"
+    }
+
+    fn name(&self) -> &str {
+        "Base91 Encoder"
+    }
+
+    fn description(&self) -> &str {
+        "Encode input to basE91"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut encoded = Vec::with_capacity(input.len() * 16 / 13 + 2);
+        let mut buffer = 0u32;
+        let mut bits = 0;
+        for &byte in input {
+            buffer |= (byte as u32) << bits;
+            bits += 8;
+            if bits > 13 {
+                // take 13 bits if that leaves a value above 88, else 14
+                let mut value = buffer & 8191;
+                if value > 88 {
+                    buffer >>= 13;
+                    bits -= 13;
+                } else {
+                    value = buffer & 16383;
+                    buffer >>= 14;
+                    bits -= 14;
+                }
+                encoded.push(ALPHABET[(value % 91) as usize]);
+                encoded.push(ALPHABET[(value / 91) as usize]);
+            }
+        }
+        if bits > 0 {
+            encoded.push(ALPHABET[(buffer % 91) as usize]);
+            if bits > 7 || buffer > 90 {
+                encoded.push(ALPHABET[(buffer / 91) as usize]);
+            }
+        }
+        Ok(encoded)
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self::default())
+    }
+}
+
+register_module!(Base91Decoder, "base91-decode");
+
+impl Module for Base91Decoder {
+    fn key(&self) -> &'static str {
+        "base91-decode"
+    }
+
+    fn name(&self) -> &str {
+        "Base91 Decoder"
+    }
+
+    fn description(&self) -> &str {
+        "Decode input from basE91"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut decoded = Vec::with_capacity(input.len() * 14 / 16 + 1);
+        let mut buffer = 0u32;
+        let mut bits = 0;
+        // first character of a pair
+        let mut pending: Option<u32> = None;
+        for (position, &symbol) in input.iter().enumerate() {
+            if symbol.is_ascii_whitespace() {
+                continue;
+            }
+            let digit = ALPHABET
+                .iter()
+                .position(|&c| c == symbol)
+                .ok_or_else(|| invalid_symbol("Base91", symbol, position))?
+                as u32;
+            let Some(low) = pending.take() else {
+                pending = Some(digit);
+                continue;
+            };
+            let value = low + digit * 91;
+            buffer |= value << bits;
+            bits += if value & 8191 > 88 { 13 } else { 14 };
+            while bits > 7 {
+                decoded.push(buffer as u8);
+                buffer >>= 8;
+                bits -= 8;
+            }
+        }
+        if let Some(low) = pending {
+            decoded.push((buffer | low << bits) as u8);
+        }
+        Ok(decoded)
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self::default())
+    }
+}
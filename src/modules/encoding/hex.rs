@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+use uuid::Uuid;
+
+use crate::modules::{
+    args::{Arg, Kind},
+    input::parse_hex,
+    register_module, Category, Module,
+};
+
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum Delimiter {
+    #[strum(to_string = "None")]
+    None = 0,
+    #[default]
+    #[strum(to_string = "Space")]
+    Space = 1,
+    #[strum(to_string = "Colon")]
+    Colon = 2,
+    #[strum(to_string = "Comma")]
+    Comma = 3,
+    /// `\x41\x42`, as in C and Python string literals.
+    #[strum(to_string = "\\x")]
+    EscapeX = 4,
+    /// `0x41, 0x42`, as in C array initializers.
+    #[strum(to_string = "0x")]
+    Prefix0x = 5,
+    /// One byte per line.
+    #[strum(to_string = "Newline")]
+    Newline = 6,
+}
+
+impl Delimiter {
+    /// Text before each byte and between two bytes on the same line.
+    fn parts(self) -> (&'static str, &'static str) {
+        match self {
+            Delimiter::None => ("", ""),
+            Delimiter::Space => ("", " "),
+            Delimiter::Colon => ("", ":"),
+            Delimiter::Comma => ("", ","),
+            Delimiter::EscapeX => ("\\x", ""),
+            Delimiter::Prefix0x => ("0x", ", "),
+            Delimiter::Newline => ("", "\n"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ToHex {
+    id: String,
+    delimiter: Delimiter,
+    uppercase: bool,
+    /// 0 keeps all bytes on one line.
+    bytes_per_line: usize,
+}
+
+pub struct FromHex {
+    id: String,
+}
+
+impl Default for ToHex {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            delimiter: Delimiter::default(),
+            uppercase: false,
+            bytes_per_line: 0,
+        }
+    }
+}
+
+impl Default for FromHex {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+        }
+    }
+}
+
+register_module!(ToHex, "to-hex");
+
+impl Module for ToHex {
+    fn key(&self) -> &'static str {
+        "to-hex"
+    }
+
+    fn name(&self) -> &str {
+        "To Hex"
+    }
+
+    fn description(&self) -> &str {
+        "Encode input as hex digits"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let (prefix, separator) = self.delimiter.parts();
+        let line_length = match self.bytes_per_line {
+            0 => input.len().max(1),
+            bytes => bytes,
+        };
+        let lines: Vec<String> = input
+            .chunks(line_length)
+            .map(|line| {
+                line.iter()
+                    .map(|byte| {
+                        if self.uppercase {
+                            format!("{}{:02X}", prefix, byte)
+                        } else {
+                            format!("{}{:02x}", prefix, byte)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(separator)
+            })
+            .collect();
+        Ok(lines.join("\n").into_bytes())
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        vec![
+            Arg::new("delimiter", "Delimiter", Kind::Enum(&mut self.delimiter)),
+            Arg::new("uppercase", "Upper case", Kind::Bool(&mut self.uppercase)),
+            Arg::new(
+                "bytes_per_line",
+                "Bytes per line",
+                Kind::Int(&mut self.bytes_per_line, 0..=4096),
+            )
+            .hint("0 keeps everything on one line"),
+        ]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+register_module!(FromHex, "from-hex");
+
+impl Module for FromHex {
+    fn key(&self) -> &'static str {
+        "from-hex"
+    }
+
+    fn name(&self) -> &str {
+        "From Hex"
+    }
+
+    fn description(&self) -> &str {
+        "Decode hex digits with any of the To Hex delimiters"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        // the delimiter needs no setting: whitespace, `:` and `,` are
+        // skipped and `0x` and `\x` prefixes accepted wherever they appear
+        parse_hex(&String::from_utf8_lossy(input))
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self::default())
+    }
+}
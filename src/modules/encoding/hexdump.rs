@@ -0,0 +1,365 @@
+use anyhow::{anyhow, bail};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+use uuid::Uuid;
+
+use crate::modules::{
+    args::{Arg, Kind},
+    register_module, Category, Module,
+};
+
+/// Most bytes a `*` line may stand for, so a mistyped offset cannot
+/// exhaust memory.
+const MAX_SQUEEZED: usize = 1 << 28;
+
+/// Output layouts. None of them squeezes repeated lines into `*`, as with
+/// `hexdump -v` and `od -v`, but [`FromHexdump`] reads squeezed dumps.
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum Style {
+    /// `00000000: 4865 6c6c 6f0a  Hello.`
+    #[default]
+    #[strum(to_string = "xxd")]
+    Xxd = 0,
+    /// `00000000  48 65 6c 6c 6f 0a  |Hello.|`, then the total length.
+    #[strum(to_string = "hexdump -C")]
+    Canonical = 1,
+    /// `000000 48 65 6c 6c 6f 0a  >Hello.<` as printed by
+    /// `od -A x -t x1z`, then the total length.
+    #[strum(to_string = "od")]
+    Od = 2,
+}
+
+#[derive(Clone)]
+pub struct ToHexdump {
+    id: String,
+    style: Style,
+    width: usize,
+}
+
+pub struct FromHexdump {
+    id: String,
+}
+
+impl Default for ToHexdump {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            style: Style::default(),
+            width: 16,
+        }
+    }
+}
+
+impl Default for FromHexdump {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+        }
+    }
+}
+
+/// The ASCII column: printable characters as they are, the rest as `.`.
+fn printable(line: &[u8]) -> String {
+    line.iter()
+        .map(|&byte| match byte {
+            0x20..=0x7e => byte as char,
+            _ => '.',
+        })
+        .collect()
+}
+
+register_module!(ToHexdump, "to-hexdump");
+
+impl Module for ToHexdump {
+    fn key(&self) -> &'static str {
+        "to-hexdump"
+    }
+
+    fn name(&self) -> &str {
+        "To Hexdump"
+    }
+
+    fn description(&self) -> &str {
+        "Show input as offset, hex and ASCII columns like xxd, hexdump -C or od"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let width = self.width.max(1);
+        let mut dump = String::new();
+        for (index, line) in input.chunks(width).enumerate() {
+            let offset = index * width;
+            let hex: Vec<String> = line.iter().map(|byte| format!("{:02x}", byte)).collect();
+            match self.style {
+                Style::Xxd => {
+                    let groups: Vec<String> = hex.chunks(2).map(|group| group.concat()).collect();
+                    let columns = width * 2 + (width - 1) / 2;
+                    dump += &format!(
+                        "{:08x}: {:<columns$}  {}\n",
+                        offset,
+                        groups.join(" "),
+                        printable(line)
+                    );
+                }
+                Style::Canonical => {
+                    dump += &format!("{:08x}  ", offset);
+                    for column in 0..width {
+                        match hex.get(column) {
+                            Some(byte) => dump += &format!("{} ", byte),
+                            None => dump += "   ",
+                        }
+                        // the gap between the two halves of the line
+                        if width > 1 && column + 1 == width / 2 {
+                            dump.push(' ');
+                        }
+                    }
+                    dump += &format!(" |{}|\n", printable(line));
+                }
+                Style::Od => {
+                    dump += &format!("{:06x}", offset);
+                    for column in 0..width {
+                        match hex.get(column) {
+                            Some(byte) => dump += &format!(" {}", byte),
+                            None => dump += "   ",
+                        }
+                    }
+                    dump += &format!("  >{}<\n", printable(line));
+                }
+            }
+        }
+        match self.style {
+            Style::Canonical if !input.is_empty() => dump += &format!("{:08x}\n", input.len()),
+            Style::Od if !input.is_empty() => dump += &format!("{:06x}\n", input.len()),
+            _ => {}
+        }
+        Ok(dump.into_bytes())
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        vec![
+            Arg::new("style", "Style", Kind::Enum(&mut self.style)),
+            Arg::new(
+                "width",
+                "Bytes per line",
+                Kind::Int(&mut self.width, 1..=256),
+            ),
+        ]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+/// A parsed line of a hexdump, with its 1-based line number.
+enum Line<'a> {
+    Data {
+        number: usize,
+        offset: &'a str,
+        bytes: Vec<u8>,
+    },
+    /// `*`: the previous line repeats up to the next offset.
+    Repeat { number: usize },
+}
+
+fn parse_line(number: usize, line: &str) -> anyhow::Result<Option<Line<'_>>> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    if line == "*" {
+        return Ok(Some(Line::Repeat { number }));
+    }
+    let split = line
+        .find(|c: char| c == ':' || c.is_ascii_whitespace())
+        .unwrap_or(line.len());
+    let offset = &line[..split];
+    if !offset.bytes().all(|c| c.is_ascii_hexdigit()) {
+        bail!("Line {}: '{}' is not an offset", number, offset);
+    }
+    let rest = &line[split..];
+    let xxd = rest.starts_with(':');
+    // cut off the ASCII column, which may hold anything including `|`
+    let hex = if xxd {
+        // xxd puts two spaces before it
+        rest[1..]
+            .trim_start()
+            .split("  ")
+            .next()
+            .unwrap_or_default()
+    } else if rest.ends_with('|') {
+        rest.split('|').next().unwrap_or_default()
+    } else if rest.ends_with('<') {
+        rest.split('>').next().unwrap_or_default()
+    } else {
+        rest
+    };
+    let mut bytes = Vec::new();
+    for group in hex.split_whitespace() {
+        // xxd groups bytes in file order; the words of plain `od`, `od -x`
+        // and `hexdump` are octal or in the byte order of the machine
+        if !xxd && group.len() != 2 {
+            bail!(
+                "Line {}: '{}' is not a single byte, dump with xxd, hexdump -C or od -t x1",
+                number,
+                group
+            );
+        }
+        match hex::decode(group) {
+            Ok(decoded) => bytes.extend(decoded),
+            Err(_) => bail!("Line {}: '{}' is not hex", number, group),
+        }
+    }
+    Ok(Some(Line::Data {
+        number,
+        offset,
+        bytes,
+    }))
+}
+
+/// Radix of the offsets: the first of hex, octal (`od -t x1`) and decimal
+/// (`od -A d -t x1`) that fits the distance between every two consecutive
+/// lines.
+fn offset_radix(lines: &[Line]) -> anyhow::Result<u32> {
+    let fits = |first: &str, second: &str, length: usize, radix: u32| match (
+        usize::from_str_radix(first, radix),
+        usize::from_str_radix(second, radix),
+    ) {
+        (Ok(first), Ok(second)) => second.checked_sub(first) == Some(length),
+        _ => false,
+    };
+    // the first line whose offset does not follow from the line before
+    let mismatch = |radix| {
+        lines.windows(2).find_map(|pair| match pair {
+            [Line::Data {
+                offset: first,
+                bytes,
+                ..
+            }, Line::Data {
+                number,
+                offset: second,
+                ..
+            }] if !fits(first, second, bytes.len(), radix) => Some((*number, *second)),
+            _ => None,
+        })
+    };
+    if let Some(radix) = [16, 8, 10]
+        .into_iter()
+        .find(|&radix| mismatch(radix).is_none())
+    {
+        return Ok(radix);
+    }
+    match mismatch(16) {
+        Some((number, offset)) => bail!(
+            "Line {}: offset '{}' does not follow from the line before",
+            number,
+            offset
+        ),
+        None => unreachable!("hex offsets fit"),
+    }
+}
+
+register_module!(FromHexdump, "from-hexdump");
+
+impl Module for FromHexdump {
+    fn key(&self) -> &'static str {
+        "from-hexdump"
+    }
+
+    fn name(&self) -> &str {
+        "From Hexdump"
+    }
+
+    fn description(&self) -> &str {
+        "Turn an xxd, hexdump -C or od -t x1 dump back into bytes"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let text = String::from_utf8_lossy(input);
+        let mut lines = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            lines.extend(parse_line(index + 1, line)?);
+        }
+        // only a dump of nothing, such as `od` prints for an empty file,
+        // has no bytes; plain hex would otherwise read as one bare offset
+        let empty = lines.iter().all(|line| match line {
+            Line::Data { offset, bytes, .. } => {
+                bytes.is_empty() && offset.bytes().all(|c| c == b'0')
+            }
+            Line::Repeat { .. } => false,
+        });
+        let has_bytes = lines
+            .iter()
+            .any(|line| matches!(line, Line::Data { bytes, .. } if !bytes.is_empty()));
+        if !empty && !has_bytes {
+            bail!("No hex bytes found, the input is not an xxd, hexdump -C or od -t x1 dump");
+        }
+        let radix = offset_radix(&lines)?;
+        let mut decoded = Vec::new();
+        let mut start = None;
+        let mut previous: &[u8] = &[];
+        let mut repeat = None;
+        for line in &lines {
+            let (number, offset, bytes) = match line {
+                Line::Repeat { number } => {
+                    repeat = Some(*number);
+                    continue;
+                }
+                Line::Data {
+                    number,
+                    offset,
+                    bytes,
+                } => (*number, offset, bytes),
+            };
+            let offset = usize::from_str_radix(offset, radix)
+                .map_err(|_| anyhow!("Line {}: '{}' is not an offset", number, offset))?;
+            // dumps of part of a file, like `xxd -s`, need not start at 0
+            let start = *start.get_or_insert(offset);
+            if repeat.take().is_some() {
+                let end = offset
+                    .checked_sub(start)
+                    .filter(|&end| end >= decoded.len())
+                    .ok_or_else(|| anyhow!("Line {}: offset goes backwards", number))?;
+                if end - decoded.len() > MAX_SQUEEZED {
+                    bail!("Line {}: '*' would repeat too many bytes", number);
+                }
+                if !previous.is_empty() {
+                    while decoded.len() < end {
+                        decoded.extend_from_slice(previous);
+                    }
+                    decoded.truncate(end);
+                }
+            }
+            decoded.extend_from_slice(bytes);
+            if !bytes.is_empty() {
+                previous = bytes;
+            }
+        }
+        if let Some(number) = repeat {
+            bail!("Line {}: '*' is not followed by an offset", number);
+        }
+        Ok(decoded)
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self::default())
+    }
+}
@@ -1 +1,89 @@
+use anyhow::anyhow;
+
+pub mod base32;
+pub mod base45;
+pub mod base58;
+pub mod base62;
 pub mod base64;
+pub mod base85;
+pub mod base91;
+pub mod hex;
+pub mod hexdump;
+pub mod radix;
+
+/// Error for a byte of encoded input that is not part of the alphabet.
+fn invalid_symbol(codec: &str, byte: u8, position: usize) -> anyhow::Error {
+    if byte.is_ascii_graphic() {
+        anyhow!(
+            "Invalid {}: '{}' at position {} is not in the alphabet",
+            codec,
+            byte as char,
+            position
+        )
+    } else {
+        anyhow!(
+            "Invalid {}: byte 0x{:02x} at position {} is not in the alphabet",
+            codec,
+            byte,
+            position
+        )
+    }
+}
+
+/// Encodes bytes as one big-endian number in base `alphabet.len()`. Each
+/// leading zero byte becomes a leading zero digit, as in Base58.
+fn encode_radix(input: &[u8], alphabet: &[u8]) -> Vec<u8> {
+    let base = alphabet.len() as u32;
+    let zeros = input.iter().take_while(|&&byte| byte == 0).count();
+    // least significant first
+    let mut digits: Vec<u32> = Vec::new();
+    for &byte in &input[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += *digit << 8;
+            *digit = carry % base;
+            carry /= base;
+        }
+        while carry > 0 {
+            digits.push(carry % base);
+            carry /= base;
+        }
+    }
+    let mut encoded = vec![alphabet[0]; zeros];
+    encoded.extend(digits.iter().rev().map(|&digit| alphabet[digit as usize]));
+    encoded
+}
+
+/// Inverse of [`encode_radix`], skipping whitespace.
+fn decode_radix(input: &[u8], alphabet: &[u8], codec: &str) -> anyhow::Result<Vec<u8>> {
+    let base = alphabet.len() as u32;
+    let mut zeros = 0;
+    // least significant first
+    let mut bytes: Vec<u32> = Vec::new();
+    for (position, &symbol) in input.iter().enumerate() {
+        if symbol.is_ascii_whitespace() {
+            continue;
+        }
+        let value = alphabet
+            .iter()
+            .position(|&c| c == symbol)
+            .ok_or_else(|| invalid_symbol(codec, symbol, position))? as u32;
+        if value == 0 && bytes.is_empty() {
+            zeros += 1;
+            continue;
+        }
+        let mut carry = value;
+        for byte in bytes.iter_mut() {
+            carry += *byte * base;
+            *byte = carry & 0xff;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry & 0xff);
+            carry >>= 8;
+        }
+    }
+    let mut decoded = vec![0; zeros];
+    decoded.extend(bytes.iter().rev().map(|&byte| byte as u8));
+    Ok(decoded)
+}
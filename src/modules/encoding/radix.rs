@@ -0,0 +1,663 @@
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+use uuid::Uuid;
+
+use crate::modules::{
+    args::{Arg, Kind},
+    register_module, Category, Module,
+};
+
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+#[derive(EnumIter, Display, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+enum Delimiter {
+    /// Groups run together, always zero-padded so they can be split again.
+    #[strum(to_string = "None")]
+    None = 0,
+    #[default]
+    #[strum(to_string = "Space")]
+    Space = 1,
+    #[strum(to_string = "Comma")]
+    Comma = 2,
+    #[strum(to_string = "Colon")]
+    Colon = 3,
+    #[strum(to_string = "Semicolon")]
+    Semicolon = 4,
+    #[strum(to_string = "Newline")]
+    Newline = 5,
+}
+
+impl Delimiter {
+    fn separator(self) -> &'static str {
+        match self {
+            Delimiter::None => "",
+            Delimiter::Space => " ",
+            Delimiter::Comma => ",",
+            Delimiter::Colon => ":",
+            Delimiter::Semicolon => ";",
+            Delimiter::Newline => "\n",
+        }
+    }
+}
+
+/// Number of digits of the largest `bits`-bit group in `radix`.
+fn width(radix: usize, bits: usize) -> usize {
+    let mut value = (1usize << bits) - 1;
+    let mut digits = 1;
+    while value >= radix {
+        value /= radix;
+        digits += 1;
+    }
+    digits
+}
+
+/// Output arguments shared by the To modules. The input is read as a
+/// stream of bits and every `bits` of them become one number, so 8 writes
+/// a number per byte and 4 one per nibble. A final short group is padded
+/// with zero bits, which the From modules drop again.
+#[derive(Clone)]
+struct Layout {
+    delimiter: Delimiter,
+    bits: usize,
+    pad: bool,
+    uppercase: bool,
+}
+
+impl Layout {
+    fn new(pad: bool) -> Self {
+        Self {
+            delimiter: Delimiter::default(),
+            bits: 8,
+            pad,
+            uppercase: false,
+        }
+    }
+
+    /// `radix` is set by To Base N, which also offers upper case letters.
+    fn args(&mut self, radix: Option<usize>) -> Vec<Arg<'_>> {
+        let delimited = self.delimiter != Delimiter::None;
+        let mut args = vec![
+            Arg::new("delimiter", "Delimiter", Kind::Enum(&mut self.delimiter)),
+            bits_arg(&mut self.bits),
+            Arg::new("pad", "Zero padding", Kind::Bool(&mut self.pad))
+                .hint("Write every group with the same number of digits")
+                .shown_if(delimited),
+        ];
+        if let Some(radix) = radix {
+            args.push(
+                Arg::new("uppercase", "Upper case", Kind::Bool(&mut self.uppercase))
+                    .shown_if(radix > 10),
+            );
+        }
+        args
+    }
+
+    fn encode(&self, input: &[u8], radix: usize) -> Vec<u8> {
+        let bits = self.bits.clamp(1, 8);
+        let mask = (1u32 << bits) - 1;
+        let mut groups = Vec::with_capacity(input.len() * 8 / bits + 1);
+        let mut buffer = 0u32;
+        let mut buffered = 0;
+        for &byte in input {
+            buffer = (buffer << 8 | byte as u32) & 0xffff;
+            buffered += 8;
+            while buffered >= bits {
+                buffered -= bits;
+                groups.push(buffer >> buffered & mask);
+            }
+        }
+        if buffered > 0 {
+            groups.push(buffer << (bits - buffered) & mask);
+        }
+        let padded = match self.pad || self.delimiter == Delimiter::None {
+            true => width(radix, bits),
+            false => 1,
+        };
+        let numbers: Vec<String> = groups
+            .into_iter()
+            .map(|group| {
+                let mut value = group as usize;
+                let mut digits = Vec::new();
+                while value > 0 || digits.len() < padded {
+                    digits.push(DIGITS[value % radix]);
+                    value /= radix;
+                }
+                if self.uppercase {
+                    digits.make_ascii_uppercase();
+                }
+                digits.iter().rev().map(|&digit| digit as char).collect()
+            })
+            .collect();
+        numbers.join(self.delimiter.separator()).into_bytes()
+    }
+}
+
+fn bits_arg(bits: &mut usize) -> Arg<'_> {
+    Arg::new("bits", "Bits per group", Kind::Int(bits, 1..=8))
+        .hint("8 for one number per byte, 4 for one per nibble")
+}
+
+/// Value of `symbol` as a digit in `radix`, ignoring case.
+fn digit(symbol: u8, radix: usize) -> Option<usize> {
+    let value = DIGITS
+        .iter()
+        .position(|&c| c == symbol.to_ascii_lowercase())?;
+    (value < radix).then_some(value)
+}
+
+/// Reads numbers of `bits` bits each back into bytes. Anything that is not
+/// a digit separates numbers, and `0b`, `0o` and `0x` prefixes are skipped
+/// where the letter is not itself a digit. Runs of digits longer than one
+/// zero-padded group are split into groups.
+fn decode(input: &[u8], radix: usize, bits: usize, codec: &str) -> anyhow::Result<Vec<u8>> {
+    let bits = bits.clamp(1, 8);
+    let width = width(radix, bits);
+    let mut decoded = Vec::with_capacity(input.len() * bits / 8 / width + 1);
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+    let mut index = 0;
+    while index < input.len() {
+        let prefixed = input[index] == b'0'
+            && matches!(
+                input.get(index + 1),
+                Some(b'b' | b'B' | b'o' | b'O' | b'x' | b'X')
+            )
+            && digit(input[index + 1], radix).is_none()
+            && input
+                .get(index + 2)
+                .is_some_and(|&symbol| digit(symbol, radix).is_some());
+        if prefixed {
+            index += 2;
+        }
+        if digit(input[index], radix).is_none() {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        while index < input.len() && digit(input[index], radix).is_some() {
+            index += 1;
+        }
+        let run = &input[start..index];
+        if run.len() > width && !run.len().is_multiple_of(width) {
+            bail!(
+                "Invalid {}: '{}' at position {} does not split into groups of {} digits",
+                codec,
+                String::from_utf8_lossy(run),
+                start,
+                width
+            );
+        }
+        for (chunk, group) in run.chunks(width).enumerate() {
+            let value = group
+                .iter()
+                .try_fold(0usize, |value, &symbol| {
+                    value.checked_mul(radix)?.checked_add(digit(symbol, radix)?)
+                })
+                .filter(|&value| value >> bits == 0);
+            let Some(value) = value else {
+                bail!(
+                    "Invalid {}: '{}' at position {} does not fit in {} bits",
+                    codec,
+                    String::from_utf8_lossy(group),
+                    start + chunk * width,
+                    bits
+                );
+            };
+            buffer = (buffer << bits | value as u32) & 0xffff;
+            buffered += bits;
+            if buffered >= 8 {
+                buffered -= 8;
+                decoded.push((buffer >> buffered) as u8);
+            }
+        }
+    }
+    Ok(decoded)
+}
+
+#[derive(Clone)]
+pub struct ToBinary {
+    id: String,
+    layout: Layout,
+}
+
+#[derive(Clone)]
+pub struct FromBinary {
+    id: String,
+    bits: usize,
+}
+
+#[derive(Clone)]
+pub struct ToOctal {
+    id: String,
+    layout: Layout,
+}
+
+#[derive(Clone)]
+pub struct FromOctal {
+    id: String,
+    bits: usize,
+}
+
+#[derive(Clone)]
+pub struct ToDecimal {
+    id: String,
+    layout: Layout,
+}
+
+#[derive(Clone)]
+pub struct FromDecimal {
+    id: String,
+    bits: usize,
+}
+
+#[derive(Clone)]
+pub struct ToBaseN {
+    id: String,
+    radix: usize,
+    layout: Layout,
+}
+
+#[derive(Clone)]
+pub struct FromBaseN {
+    id: String,
+    radix: usize,
+    bits: usize,
+}
+
+impl Default for ToBinary {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            layout: Layout::new(true),
+        }
+    }
+}
+
+impl Default for FromBinary {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            bits: 8,
+        }
+    }
+}
+
+impl Default for ToOctal {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            layout: Layout::new(true),
+        }
+    }
+}
+
+impl Default for FromOctal {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            bits: 8,
+        }
+    }
+}
+
+impl Default for ToDecimal {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            layout: Layout::new(false),
+        }
+    }
+}
+
+impl Default for FromDecimal {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            bits: 8,
+        }
+    }
+}
+
+impl Default for ToBaseN {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            radix: 36,
+            layout: Layout::new(true),
+        }
+    }
+}
+
+impl Default for FromBaseN {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            radix: 36,
+            bits: 8,
+        }
+    }
+}
+
+register_module!(ToBinary, "to-binary");
+
+impl Module for ToBinary {
+    fn key(&self) -> &'static str {
+        "to-binary"
+    }
+
+    fn name(&self) -> &str {
+        "To Binary"
+    }
+
+    fn description(&self) -> &str {
+        "Write input as binary numbers"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        Ok(self.layout.encode(input, 2))
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        self.layout.args(None)
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+register_module!(FromBinary, "from-binary");
+
+impl Module for FromBinary {
+    fn key(&self) -> &'static str {
+        "from-binary"
+    }
+
+    fn name(&self) -> &str {
+        "From Binary"
+    }
+
+    fn description(&self) -> &str {
+        "Read binary numbers back into bytes"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        decode(input, 2, self.bits, "Binary")
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        vec![bits_arg(&mut self.bits)]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+register_module!(ToOctal, "to-octal");
+
+impl Module for ToOctal {
+    fn key(&self) -> &'static str {
+        "to-octal"
+    }
+
+    fn name(&self) -> &str {
+        "To Octal"
+    }
+
+    fn description(&self) -> &str {
+        "Write input as octal numbers"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        Ok(self.layout.encode(input, 8))
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        self.layout.args(None)
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+register_module!(FromOctal, "from-octal");
+
+impl Module for FromOctal {
+    fn key(&self) -> &'static str {
+        "from-octal"
+    }
+
+    fn name(&self) -> &str {
+        "From Octal"
+    }
+
+    fn description(&self) -> &str {
+        "Read octal numbers back into bytes"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        decode(input, 8, self.bits, "Octal")
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        vec![bits_arg(&mut self.bits)]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+register_module!(ToDecimal, "to-decimal");
+
+impl Module for ToDecimal {
+    fn key(&self) -> &'static str {
+        "to-decimal"
+    }
+
+    fn name(&self) -> &str {
+        "To Decimal"
+    }
+
+    fn description(&self) -> &str {
+        "Write input as decimal numbers"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        Ok(self.layout.encode(input, 10))
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        self.layout.args(None)
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+register_module!(FromDecimal, "from-decimal");
+
+impl Module for FromDecimal {
+    fn key(&self) -> &'static str {
+        "from-decimal"
+    }
+
+    fn name(&self) -> &str {
+        "From Decimal"
+    }
+
+    fn description(&self) -> &str {
+        "Read decimal numbers back into bytes"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        decode(input, 10, self.bits, "Decimal")
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        vec![bits_arg(&mut self.bits)]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+register_module!(ToBaseN, "to-base-n");
+
+impl Module for ToBaseN {
+    fn key(&self) -> &'static str {
+        "to-base-n"
+    }
+
+    fn name(&self) -> &str {
+        "To Base N"
+    }
+
+    fn description(&self) -> &str {
+        "Write input as numbers in any base from 2 to 36"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        Ok(self.layout.encode(input, self.radix.clamp(2, 36)))
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        let radix = self.radix;
+        let mut args = vec![Arg::new(
+            "radix",
+            "Base",
+            Kind::Int(&mut self.radix, 2..=36),
+        )];
+        args.extend(self.layout.args(Some(radix)));
+        args
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+register_module!(FromBaseN, "from-base-n");
+
+impl Module for FromBaseN {
+    fn key(&self) -> &'static str {
+        "from-base-n"
+    }
+
+    fn name(&self) -> &str {
+        "From Base N"
+    }
+
+    fn description(&self) -> &str {
+        "Read numbers in any base from 2 to 36 back into bytes"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encoding
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let radix = self.radix.clamp(2, 36);
+        decode(input, radix, self.bits, &format!("Base {}", radix))
+    }
+
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        vec![
+            Arg::new("radix", "Base", Kind::Int(&mut self.radix, 2..=36)),
+            bits_arg(&mut self.bits),
+        ]
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(Self {
+            id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        })
+    }
+}
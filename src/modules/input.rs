@@ -1,9 +1,15 @@
+use std::fs;
+
+use anyhow::{anyhow, bail};
+use base64::Engine;
+#[cfg(feature = "gui")]
 use eframe::{egui, egui::Id};
+use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter};
 use uuid::Uuid;
 
-#[derive(Default, EnumIter, Display, PartialEq, Eq, Clone, Copy)]
+#[derive(Default, EnumIter, Display, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum InputType {
     #[default]
     #[strum(to_string = "Text")]
@@ -12,9 +18,45 @@ pub enum InputType {
     Hex = 1,
     #[strum(to_string = "Base64")]
     Base64 = 2,
+    #[strum(to_string = "UTF-16LE")]
+    Utf16Le = 3,
+    #[strum(to_string = "UTF-16BE")]
+    Utf16Be = 4,
+    #[strum(to_string = "Latin-1")]
+    Latin1 = 5,
+    /// Byte values such as `1,2,3` or `65 66 67`.
+    #[strum(to_string = "Decimal")]
+    Decimal = 6,
+    /// Text with `\x41`, `\n`, `\u{e9}` and similar escapes.
+    #[strum(to_string = "Escaped")]
+    Escaped = 7,
+    /// The contents of the file at the given path.
+    #[strum(to_string = "File")]
+    File = 8,
+}
+
+impl InputType {
+    /// Prefix selecting this type on the command line, e.g. `hex:`.
+    pub fn prefix(self) -> &'static str {
+        match self {
+            InputType::Text => "text:",
+            InputType::Hex => "hex:",
+            InputType::Base64 => "base64:",
+            InputType::Utf16Le => "utf16le:",
+            InputType::Utf16Be => "utf16be:",
+            InputType::Latin1 => "latin1:",
+            InputType::Decimal => "decimal:",
+            InputType::Escaped => "escaped:",
+            InputType::File => "file:",
+        }
+    }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct Argument {
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    #[serde(skip)]
     id: String,
     value: String,
     input_type: InputType,
@@ -30,22 +72,83 @@ impl Default for Argument {
     }
 }
 
+impl Clone for Argument {
+    /// The copy gets its own id so both can be shown side by side.
+    fn clone(&self) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            value: self.value.clone(),
+            input_type: self.input_type,
+        }
+    }
+}
+
 impl Argument {
-    pub fn try_to_vec(&self) -> anyhow::Result<Vec<u8>> {
-        match self.input_type {
-            InputType::Text => Ok(self.value.as_bytes().to_vec()),
-            InputType::Hex => Ok(hex::decode(&self.value)?),
-            InputType::Base64 => Ok(base64::decode(&self.value)?),
+    /// Reads a command line value: an [`InputType::prefix`] followed by the
+    /// value, or plain text without a prefix.
+    pub fn parse(value: &str) -> Self {
+        let (input_type, value) = InputType::iter()
+            .find_map(|input_type| Some((input_type, value.strip_prefix(input_type.prefix())?)))
+            .unwrap_or((InputType::Text, value));
+        Self {
+            value: value.to_string(),
+            input_type,
+            ..Self::default()
         }
     }
 
-    pub fn try_to_str(&self) -> anyhow::Result<String> {
-        let bytes = self.try_to_vec()?;
-        Ok(String::from_utf8(bytes)?)
+    /// Whether the value is read from a file. Such values are only decoded
+    /// when baking, not for feedback shown on every frame.
+    pub fn is_file(&self) -> bool {
+        self.input_type == InputType::File
+    }
+
+    /// Decodes the value. Errors start with `field`, the argument's label,
+    /// e.g. "Key: invalid hex at position 7".
+    pub fn try_to_vec(&self, field: &str) -> anyhow::Result<Vec<u8>> {
+        let value = self.value.as_str();
+        let bytes = match self.input_type {
+            InputType::Text => Ok(value.as_bytes().to_vec()),
+            InputType::Hex => parse_hex(value),
+            InputType::Base64 => {
+                let compact: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+                base64::engine::general_purpose::STANDARD
+                    .decode(compact)
+                    .map_err(|e| anyhow!("invalid Base64: {}", e))
+            }
+            InputType::Utf16Le => Ok(value.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+            InputType::Utf16Be => Ok(value.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+            InputType::Latin1 => value
+                .chars()
+                .enumerate()
+                .map(|(position, c)| {
+                    u8::try_from(c).map_err(|_| {
+                        anyhow!(
+                            "'{}' at position {} is not a Latin-1 character",
+                            c,
+                            position
+                        )
+                    })
+                })
+                .collect(),
+            InputType::Decimal => parse_decimal(value),
+            InputType::Escaped => parse_escaped(value),
+            InputType::File => fs::read(value).map_err(|e| anyhow!("reading {}: {}", value, e)),
+        };
+        bytes.map_err(|e| anyhow!("{}: {}", field, e))
     }
 
-    pub fn show(&mut self, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
+    /// Decodes the value as UTF-8 text, with errors as in [`Self::try_to_vec`].
+    pub fn try_to_str(&self, field: &str) -> anyhow::Result<String> {
+        let bytes = self.try_to_vec(field)?;
+        String::from_utf8(bytes).map_err(|e| anyhow!("{}: {}", field, e))
+    }
+
+    /// The type selector and value field, marked as changed when either was.
+    #[cfg(feature = "gui")]
+    pub fn show(&mut self, ui: &mut egui::Ui) -> egui::Response {
+        let previous = self.input_type;
+        let inner = ui.horizontal(|ui| {
             egui::ComboBox::from_id_salt(Id::new(&self.id))
                 .selected_text(self.input_type.to_string())
                 .show_ui(ui, |ui| {
@@ -57,7 +160,136 @@ impl Argument {
                         );
                     }
                 });
-            ui.text_edit_singleline(&mut self.value);
+            let mut changed = ui.text_edit_singleline(&mut self.value).changed();
+            if self.input_type == InputType::File && ui.button("…").clicked() {
+                if let Some(path) = rfd::FileDialog::new().pick_file() {
+                    self.value = path.display().to_string();
+                    changed = true;
+                }
+            }
+            changed
         });
+        let mut response = inner.response;
+        if inner.inner || self.input_type != previous {
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+/// Hex digits, ignoring whitespace, `:` and `,` separators and `0x` and
+/// `\x` prefixes, e.g. `00 11 22`, `00:11:22`, `0x00, 0x11` or `\x00\x11`.
+/// Each group of digits between separators must have an even length.
+pub(crate) fn parse_hex(value: &str) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let chars: Vec<char> = value.chars().collect();
+    let mut position = 0;
+    while position < chars.len() {
+        let c = chars[position];
+        if c.is_whitespace() || c == ':' || c == ',' {
+            position += 1;
+            continue;
+        }
+        let start = position;
+        if (c == '0' || c == '\\') && matches!(chars.get(position + 1), Some('x' | 'X')) {
+            position += 2;
+        }
+        let digits_start = position;
+        while let Some(&c) = chars.get(position) {
+            // `\x` starts the next byte without a separator
+            if c.is_whitespace() || c == ':' || c == ',' || c == '\\' {
+                break;
+            }
+            if !c.is_ascii_hexdigit() {
+                bail!("invalid hex at position {}", position);
+            }
+            position += 1;
+        }
+        let digits = &chars[digits_start..position];
+        if digits.is_empty() {
+            bail!("missing hex digits at position {}", start);
+        }
+        if digits.len() % 2 == 1 {
+            bail!("odd number of hex digits at position {}", start);
+        }
+        for pair in digits.chunks(2) {
+            let high = pair[0].to_digit(16).unwrap_or_default();
+            let low = pair[1].to_digit(16).unwrap_or_default();
+            bytes.push((high << 4 | low) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+/// Byte values separated by commas and/or whitespace.
+fn parse_decimal(value: &str) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut position = 0;
+    for token in value.split(|c: char| c == ',' || c.is_whitespace()) {
+        if !token.is_empty() {
+            let byte = token.parse::<u8>().map_err(|_| {
+                anyhow!(
+                    "invalid decimal byte \"{}\" at position {}",
+                    token,
+                    position
+                )
+            })?;
+            bytes.push(byte);
+        }
+        position += token.chars().count() + 1;
+    }
+    Ok(bytes)
+}
+
+/// UTF-8 text with Rust/C style escapes: `\x41`, `\n`, `\r`, `\t`, `\0`,
+/// `\\`, `\'`, `\"` and `\u{e9}`.
+fn parse_escaped(value: &str) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut chars = value.chars().enumerate();
+    while let Some((position, c)) = chars.next() {
+        if c != '\\' {
+            let mut buffer = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+        let invalid = || anyhow!("invalid escape at position {}", position);
+        match chars.next().map(|(_, c)| c) {
+            Some('n') => bytes.push(b'\n'),
+            Some('r') => bytes.push(b'\r'),
+            Some('t') => bytes.push(b'\t'),
+            Some('0') => bytes.push(0),
+            Some(c @ ('\\' | '\'' | '"')) => bytes.push(c as u8),
+            Some('x') => {
+                let digits: String = (0..2)
+                    .filter_map(|_| chars.next().map(|(_, c)| c))
+                    .collect();
+                // exactly two digits, so `\x4` is not quietly read as 0x04
+                if digits.len() != 2 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(invalid());
+                }
+                bytes.push(u8::from_str_radix(&digits, 16).map_err(|_| invalid())?);
+            }
+            Some('u') => {
+                if chars.next().map(|(_, c)| c) != Some('{') {
+                    return Err(invalid());
+                }
+                let mut digits = String::new();
+                loop {
+                    match chars.next().map(|(_, c)| c) {
+                        Some('}') => break,
+                        Some(c) => digits.push(c),
+                        None => return Err(invalid()),
+                    }
+                }
+                let c = u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(invalid)?;
+                let mut buffer = [0; 4];
+                bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            }
+            _ => return Err(invalid()),
+        }
     }
+    Ok(bytes)
 }
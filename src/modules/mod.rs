@@ -1,79 +1,106 @@
-use eframe::egui::{Color32, Frame, Id, Response, Sense, Ui};
+#[cfg(feature = "gui")]
+use eframe::egui::Ui;
+use strum_macros::{Display, EnumIter};
 
+use crate::modules::args::{Arg, Note};
+
+pub mod args;
 pub mod crypto;
 pub mod encoding;
-mod input;
+pub mod input;
+
+/// Groups modules in the module list.
+#[derive(EnumIter, Display, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Category {
+    Encoding,
+    Crypto,
+    Hashing,
+    Compression,
+    #[strum(to_string = "Data format")]
+    DataFormat,
+}
 
-pub trait Module {
+/// Modules are `Send` so a recipe can be baked on a worker thread.
+pub trait Module: Send {
+    /// Stable identifier of the module type, used in recipe files and on
+    /// the command line. Never change it once released.
+    fn key(&self) -> &'static str;
     fn name(&self) -> &str;
     fn description(&self) -> &str;
+    fn category(&self) -> Category;
     fn id(&self) -> &str;
     fn process(&self, input: &[u8]) -> anyhow::Result<Vec<u8>>;
 
-    fn render_inner(&mut self, ui: &mut Ui) {}
-
-    fn render(&mut self, ui: &mut Ui, index: usize) {
-        let background_color = if ui.visuals().dark_mode {
-            if index % 2 == 0 {
-                Color32::from_rgb(40, 60, 60)
-            } else {
-                Color32::from_rgb(30, 50, 50)
-            }
-        } else if index % 2 == 0 {
-            Color32::from_rgb(240, 220, 220)
-        } else {
-            Color32::from_rgb(230, 210, 210)
-        };
+    /// The module's arguments in display order. The default UI, recipe
+    /// serialization and `bake --arg` are all derived from this list.
+    fn args(&mut self) -> Vec<Arg<'_>> {
+        Vec::new()
+    }
 
-        Frame::none().fill(background_color).show(ui, |ui| {
-            ui.group(|ui| {
-                ui.set_min_height(32.0);
-                ui.set_min_width(ui.available_width());
-                ui.vertical(|ui| {
-                    // set drag detection area
-                    let resp = ui
-                        .horizontal(|ui| {
-                            ui.heading(self.name());
-                            ui.label(self.description());
-                        })
-                        .response;
-                    ui.interact(resp.rect, Id::new(self.id()), Sense::drag());
-                    self.render_inner(ui);
-                });
-            });
-        });
+    /// Live feedback on the argument values, e.g. a key of the wrong size.
+    fn notes(&self) -> Vec<Note> {
+        Vec::new()
     }
-    fn render_list(&self, ui: &mut Ui, index: usize) -> Response {
-        // 使用交替背景颜色
-        // light theme: 240, 220
-        // dark theme: 40, 60
 
-        let background_color = if ui.visuals().dark_mode {
-            if index % 2 == 0 {
-                Color32::from_rgb(40, 60, 60)
-            } else {
-                Color32::from_rgb(30, 50, 50)
-            }
-        } else if index % 2 == 0 {
-            Color32::from_rgb(240, 220, 220)
-        } else {
-            Color32::from_rgb(230, 210, 210)
-        };
+    /// Shows the argument widgets and tells whether any value changed.
+    #[cfg(feature = "gui")]
+    fn render_inner(&mut self, ui: &mut Ui) -> bool {
+        let id = self.id().to_string();
+        let notes = self.notes();
+        args::show(ui, &id, self.args(), notes)
+    }
 
-        let frame = Frame::none().fill(background_color).show(ui, |ui| {
-            ui.group(|ui| {
-                ui.set_min_height(32.0);
-                ui.set_min_width(ui.available_width());
-                ui.vertical(|ui| {
-                    ui.heading(self.name());
-                    ui.label(self.description());
-                    //
-                });
-            });
-        });
+    /// Exports the configured arguments so the step can be saved in a recipe.
+    fn export_args(&self) -> anyhow::Result<serde_json::Value> {
+        // `args` borrows mutably, so read them from a copy
+        args::to_json(self.clone_box().args())
+    }
 
-        frame.response
+    /// Restores arguments produced by [`Module::export_args`].
+    fn import_args(&mut self, value: serde_json::Value) -> anyhow::Result<()> {
+        args::from_json(self, value)
     }
 
     fn clone_box(&self) -> Box<dyn Module>;
 }
+
+/// A module type, submitted next to its implementation with
+/// [`register_module!`].
+pub struct Registration {
+    /// The module's [`Module::key`], so lookups need not create every module.
+    pub key: &'static str,
+    pub create: fn() -> Box<dyn Module>,
+}
+
+inventory::collect!(Registration);
+
+/// Adds a `Default` module type to [`all`] under its [`Module::key`].
+macro_rules! register_module {
+    ($module:ty, $key:literal) => {
+        inventory::submit! {
+            $crate::modules::Registration {
+                key: $key,
+                create: || Box::<$module>::default(),
+            }
+        }
+    };
+}
+pub(crate) use register_module;
+
+/// Every registered module, by category and then by name.
+pub fn all() -> Vec<Box<dyn Module>> {
+    let mut modules: Vec<_> = inventory::iter::<Registration>
+        .into_iter()
+        .map(|registration| (registration.create)())
+        .collect();
+    modules.sort_by(|a, b| (a.category(), a.name()).cmp(&(b.category(), b.name())));
+    modules
+}
+
+/// Creates the module registered under `key`, with default arguments.
+pub fn create(key: &str) -> Option<Box<dyn Module>> {
+    inventory::iter::<Registration>
+        .into_iter()
+        .find(|registration| registration.key == key)
+        .map(|registration| (registration.create)())
+}
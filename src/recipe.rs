@@ -0,0 +1,260 @@
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::modules::{self, Module};
+
+/// An ordered chain of configured modules, run without any UI.
+#[derive(Default, Clone)]
+pub struct Recipe {
+    pub steps: Vec<Step>,
+}
+
+/// A configured module plus its debugging switches.
+pub struct Step {
+    pub module: Box<dyn Module>,
+    /// Disabled steps pass their input through unchanged.
+    pub enabled: bool,
+    /// [`Recipe::bake_debug`] pauses before running this step.
+    pub breakpoint: bool,
+}
+
+/// On-disk form of a [`Recipe`].
+#[derive(Serialize, Deserialize)]
+struct RecipeFile {
+    steps: Vec<StepFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StepFile {
+    /// The module's [`Module::key`].
+    module: String,
+    /// Whatever [`Module::export_args`] produced.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    args: serde_json::Value,
+    #[serde(default = "enabled_default", skip_serializing_if = "is_true")]
+    enabled: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    breakpoint: bool,
+}
+
+fn enabled_default() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+/// The outcome of running a [`Recipe`] over some input.
+pub struct Bake {
+    /// Output of the last step that succeeded, or the input if none did.
+    pub output: Vec<u8>,
+    /// One result per step that was reached, in order. Execution stops at
+    /// the first error, so only the last entry can be [`StepResult::Failed`].
+    pub steps: Vec<StepResult>,
+    /// The step execution paused before, at a breakpoint or the end of a
+    /// "run up to here".
+    pub paused_at: Option<usize>,
+}
+
+/// Lets another thread follow and stop a running bake. Cancellation is
+/// checked between steps.
+#[derive(Default, Clone)]
+pub struct BakeControl {
+    cancelled: Arc<AtomicBool>,
+    completed: Arc<AtomicUsize>,
+    /// Breakpoints on the steps before this one are passed over.
+    resume_from: usize,
+}
+
+pub enum StepResult {
+    /// The step is disabled and passed its input through.
+    Skipped,
+    Done(Vec<u8>),
+    Failed(anyhow::Error),
+}
+
+impl Step {
+    pub fn new(module: Box<dyn Module>) -> Self {
+        Self {
+            module,
+            enabled: true,
+            breakpoint: false,
+        }
+    }
+}
+
+impl Clone for Step {
+    /// Copies the step's configuration; the module gets a fresh id.
+    fn clone(&self) -> Self {
+        Self {
+            module: self.module.clone_box(),
+            enabled: self.enabled,
+            breakpoint: self.breakpoint,
+        }
+    }
+}
+
+impl Recipe {
+    pub fn new(steps: Vec<Box<dyn Module>>) -> Self {
+        Self {
+            steps: steps.into_iter().map(Step::new).collect(),
+        }
+    }
+
+    /// Chains modules given by their [`Module::key`], with default arguments.
+    pub fn from_keys<'a>(keys: impl IntoIterator<Item = &'a str>) -> anyhow::Result<Self> {
+        let modules = keys
+            .into_iter()
+            .map(|key| modules::create(key).ok_or_else(|| anyhow!("Unknown module \"{}\"", key)))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self::new(modules))
+    }
+
+    /// Parses a JSON recipe file, e.g.
+    /// `{"steps": [{"module": "aes-decrypt", "args": {"mode": "Cbc", ...}}]}`.
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        let file: RecipeFile = serde_json::from_str(json)?;
+        let steps = file
+            .steps
+            .into_iter()
+            .enumerate()
+            .map(|(index, step)| {
+                let mut module = modules::create(&step.module)
+                    .ok_or_else(|| anyhow!("Unknown module \"{}\"", step.module))?;
+                module.import_args(step.args).with_context(|| {
+                    format!("step {} ({}): invalid arguments", index + 1, step.module)
+                })?;
+                Ok(Step {
+                    module,
+                    enabled: step.enabled,
+                    breakpoint: step.breakpoint,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { steps })
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        let steps = self
+            .steps
+            .iter()
+            .map(|step| {
+                Ok(StepFile {
+                    module: step.module.key().to_string(),
+                    args: step.module.export_args()?,
+                    enabled: step.enabled,
+                    breakpoint: step.breakpoint,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(serde_json::to_string_pretty(&RecipeFile { steps })?)
+    }
+
+    /// Runs every enabled step, ignoring breakpoints.
+    pub fn bake(&self, input: &[u8]) -> Bake {
+        self.run(input, false, self.steps.len(), &BakeControl::default())
+            .expect("bake without a shared control is never cancelled")
+    }
+
+    /// Runs the enabled steps up to and including `run_to` (or all of them),
+    /// pausing at the first breakpoint on the way that `control` does not
+    /// pass over. Returns `None` if `control` was cancelled.
+    pub fn bake_debug(
+        &self,
+        input: &[u8],
+        run_to: Option<usize>,
+        control: &BakeControl,
+    ) -> Option<Bake> {
+        let end = run_to.map_or(self.steps.len(), |index| index + 1);
+        self.run(input, true, end.min(self.steps.len()), control)
+    }
+
+    fn run(
+        &self,
+        input: &[u8],
+        breakpoints: bool,
+        end: usize,
+        control: &BakeControl,
+    ) -> Option<Bake> {
+        let mut output = input.to_vec();
+        let mut steps = Vec::with_capacity(end);
+        for (index, step) in self.steps[..end].iter().enumerate() {
+            if control.is_cancelled() {
+                return None;
+            }
+            if breakpoints && step.breakpoint && index >= control.resume_from {
+                return Some(Bake {
+                    output,
+                    steps,
+                    paused_at: Some(index),
+                });
+            }
+            control.completed.store(index, Ordering::Relaxed);
+            if !step.enabled {
+                steps.push(StepResult::Skipped);
+                continue;
+            }
+            match step.module.process(&output) {
+                Ok(new_output) => {
+                    output = new_output.clone();
+                    steps.push(StepResult::Done(new_output));
+                }
+                Err(e) => {
+                    steps.push(StepResult::Failed(e));
+                    break;
+                }
+            }
+        }
+        let mut bake = Bake {
+            output,
+            steps,
+            paused_at: None,
+        };
+        if end < self.steps.len() && bake.error().is_none() {
+            bake.paused_at = Some(end);
+        }
+        control.completed.store(end, Ordering::Relaxed);
+        Some(bake)
+    }
+}
+
+impl Bake {
+    /// The failing step's index and error, if the recipe did not finish.
+    pub fn error(&self) -> Option<(usize, &anyhow::Error)> {
+        match self.steps.last() {
+            Some(StepResult::Failed(e)) => Some((self.steps.len() - 1, e)),
+            _ => None,
+        }
+    }
+}
+
+impl BakeControl {
+    /// A control for baking again after a pause before step `paused_at`,
+    /// passing over that breakpoint and the ones before it. Use `run_to`
+    /// of `paused_at` to step over just that step.
+    pub fn resume(paused_at: usize) -> Self {
+        Self {
+            resume_from: paused_at + 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Number of steps finished so far.
+    pub fn completed(&self) -> usize {
+        self.completed.load(Ordering::Relaxed)
+    }
+}
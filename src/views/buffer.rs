@@ -0,0 +1,405 @@
+use std::ops::RangeInclusive;
+
+use eframe::egui::{
+    vec2, Align2, Event, EventFilter, FontId, Key, Pos2, Rect, Response, Sense, TextEdit,
+    TextStyle, Ui,
+};
+
+use super::copy::copy_menu;
+
+/// How a [`Buffer`] is displayed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum View {
+    #[default]
+    Text,
+    Hex,
+}
+
+const BYTES_PER_ROW: usize = 16;
+/// Character column where the hex bytes start, after the 8-digit offset.
+const HEX_COLUMN: usize = 10;
+/// Character column of the ASCII dump.
+const ASCII_COLUMN: usize = HEX_COLUMN + 3 * BYTES_PER_ROW + 2;
+const ROW_CHARS: usize = ASCII_COLUMN + BYTES_PER_ROW;
+
+/// Raw bytes shown as text or as a hex dump. The text can be edited only
+/// while the bytes are valid UTF-8; the hex view can always be edited.
+#[derive(Default)]
+pub struct Buffer {
+    bytes: Vec<u8>,
+    /// The bytes as text, with replacement characters if `binary`.
+    text: String,
+    /// The bytes are not valid UTF-8.
+    binary: bool,
+    pub view: View,
+    /// Hex view selection as `(anchor, cursor)` byte indices.
+    selection: Option<(usize, usize)>,
+    /// First hex digit typed at the cursor.
+    nibble: Option<u8>,
+}
+
+impl Buffer {
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Replaces the contents, keeping the view.
+    pub fn set(&mut self, bytes: Vec<u8>) {
+        self.bytes = bytes;
+        self.nibble = None;
+        self.bytes_changed();
+    }
+
+    fn bytes_changed(&mut self) {
+        (self.text, self.binary) = match std::str::from_utf8(&self.bytes) {
+            Ok(text) => (text.to_string(), false),
+            Err(_) => (String::from_utf8_lossy(&self.bytes).into_owned(), true),
+        };
+    }
+
+    /// Fills the available space with the current view. Returns whether the
+    /// user changed the bytes.
+    pub fn show(&mut self, ui: &mut Ui, hint: &str, editable: bool) -> bool {
+        match self.view {
+            View::Text => self.show_text(ui, hint, editable),
+            View::Hex => self.show_hex(ui, editable),
+        }
+    }
+
+    fn show_text(&mut self, ui: &mut Ui, hint: &str, editable: bool) -> bool {
+        if self.binary {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!(
+                    "{} bytes of binary data, not valid UTF-8; shown lossily",
+                    self.bytes.len()
+                ),
+            );
+        }
+
+        let rows = (ui.available_height() / ui.text_style_height(&TextStyle::Body)) as usize;
+        if editable && !self.binary {
+            let response = ui.add(
+                TextEdit::multiline(&mut self.text)
+                    .desired_width(ui.available_width())
+                    .desired_rows(rows)
+                    .hint_text(hint),
+            );
+            if response.changed() {
+                self.bytes = self.text.clone().into_bytes();
+                return true;
+            }
+        } else {
+            ui.add(
+                TextEdit::multiline(&mut self.text.as_str())
+                    .desired_width(ui.available_width())
+                    .desired_rows(rows)
+                    .hint_text(hint),
+            );
+        }
+        false
+    }
+
+    /// Offsets, hex bytes and ASCII. Click or drag to select, shift extends
+    /// the selection. When `editable`, typing hex digits overwrites the byte
+    /// at the cursor (or appends at the end), Backspace and Delete remove.
+    fn show_hex(&mut self, ui: &mut Ui, editable: bool) -> bool {
+        let font_id = TextStyle::Monospace.resolve(ui.style());
+        let (char_width, row_height) =
+            ui.fonts(|fonts| (fonts.glyph_width(&font_id, '0'), fonts.row_height(&font_id)));
+        // editable buffers have one extra cell to append to
+        let last = match (editable, self.bytes.len()) {
+            (true, len) => Some(len),
+            (false, 0) => None,
+            (false, len) => Some(len - 1),
+        };
+        let rows = last.map_or(1, |last| last / BYTES_PER_ROW + 1);
+
+        let (rect, response) = ui.allocate_exact_size(
+            vec2(ROW_CHARS as f32 * char_width, rows as f32 * row_height),
+            Sense::click_and_drag(),
+        );
+        self.selection = match (self.selection, last) {
+            (Some((anchor, cursor)), Some(last)) => Some((anchor.min(last), cursor.min(last))),
+            _ => None,
+        };
+
+        let cell_at = |pos: Pos2| {
+            let row = ((pos.y - rect.top()) / row_height).max(0.0) as usize;
+            let column = ((pos.x - rect.left()) / char_width).max(0.0) as usize;
+            let byte = if column >= ASCII_COLUMN {
+                column - ASCII_COLUMN
+            } else if column >= HEX_COLUMN {
+                // skip the gap between the two groups of eight
+                let column = column - HEX_COLUMN;
+                if column > 3 * BYTES_PER_ROW / 2 {
+                    (column - 1) / 3
+                } else {
+                    column / 3
+                }
+            } else {
+                0
+            };
+            (row * BYTES_PER_ROW + byte.min(BYTES_PER_ROW - 1)).min(last.unwrap_or(0))
+        };
+
+        if let (Some(pos), Some(_)) = (response.interact_pointer_pos(), last) {
+            if response.is_pointer_button_down_on() {
+                let index = cell_at(pos);
+                let (pressed, shift) =
+                    ui.input(|i| (i.pointer.primary_pressed(), i.modifiers.shift));
+                self.selection = match self.selection {
+                    Some((anchor, _)) if !pressed || shift => Some((anchor, index)),
+                    _ => Some((index, index)),
+                };
+                self.nibble = None;
+                response.request_focus();
+            }
+        }
+        response.context_menu(|ui| {
+            copy_menu(ui, self.selected());
+            ui.separator();
+            if ui.button("Select all").clicked() {
+                ui.close_menu();
+                self.selection = last.map(|last| (0, last));
+            }
+        });
+
+        let mut changed = false;
+        if response.has_focus() {
+            ui.memory_mut(|memory| {
+                memory.set_focus_lock_filter(
+                    response.id,
+                    EventFilter {
+                        horizontal_arrows: true,
+                        vertical_arrows: true,
+                        ..Default::default()
+                    },
+                )
+            });
+            let before = self.selection;
+            for event in ui.input(|i| i.events.clone()) {
+                if event == Event::Copy {
+                    ui.ctx().copy_text(hex::encode(self.selected()));
+                }
+                changed |= self.hex_event(&event, editable, last);
+            }
+            if let Some((_, cursor)) = self.selection.filter(|_| self.selection != before) {
+                ui.scroll_to_rect(self.cell_rect(rect, cursor, char_width, row_height), None);
+            }
+            if changed {
+                self.bytes_changed();
+            }
+        }
+
+        self.paint_hex(ui, &response, rect, &font_id, char_width, row_height);
+        changed
+    }
+
+    /// Applies a key press to the hex view. Returns whether the bytes changed.
+    fn hex_event(&mut self, event: &Event, editable: bool, last: Option<usize>) -> bool {
+        let (Some((anchor, cursor)), Some(last)) = (self.selection, last) else {
+            return false;
+        };
+        match event {
+            Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+                ..
+            } => {
+                let moved = match key {
+                    Key::ArrowLeft => Some(cursor.saturating_sub(1)),
+                    Key::ArrowRight => Some((cursor + 1).min(last)),
+                    Key::ArrowUp => Some(cursor.checked_sub(BYTES_PER_ROW).unwrap_or(cursor)),
+                    Key::ArrowDown => Some(
+                        Some(cursor + BYTES_PER_ROW)
+                            .filter(|&index| index <= last)
+                            .unwrap_or(cursor),
+                    ),
+                    Key::Home => Some(cursor - cursor % BYTES_PER_ROW),
+                    Key::End => {
+                        Some((cursor - cursor % BYTES_PER_ROW + BYTES_PER_ROW - 1).min(last))
+                    }
+                    _ => None,
+                };
+                if let Some(index) = moved {
+                    let anchor = if modifiers.shift { anchor } else { index };
+                    self.selection = Some((anchor, index));
+                    self.nibble = None;
+                    return false;
+                }
+                if !editable {
+                    return false;
+                }
+                let range = self.selected_range();
+                let removed = match key {
+                    Key::Backspace | Key::Delete if anchor != cursor => range,
+                    Key::Backspace if cursor > 0 => Some(cursor - 1..=cursor - 1),
+                    Key::Delete if cursor < self.bytes.len() => Some(cursor..=cursor),
+                    _ => None,
+                };
+                if let Some(range) = removed {
+                    let start = *range.start();
+                    self.bytes.drain(range);
+                    self.selection = Some((start, start));
+                    self.nibble = None;
+                    return true;
+                }
+            }
+            Event::Text(text) if editable => {
+                let mut changed = false;
+                for digit in text.chars().filter_map(|c| c.to_digit(16)) {
+                    let Some((_, cursor)) = self.selection else {
+                        break;
+                    };
+                    match self.nibble.take() {
+                        None => self.nibble = Some(digit as u8),
+                        Some(high) => {
+                            let byte = high << 4 | digit as u8;
+                            match self.bytes.get_mut(cursor) {
+                                Some(old) => *old = byte,
+                                None => self.bytes.push(byte),
+                            }
+                            self.selection = Some((cursor + 1, cursor + 1));
+                            changed = true;
+                        }
+                    }
+                }
+                return changed;
+            }
+            _ => {}
+        }
+        false
+    }
+
+    /// Selected byte indices, excluding the append position.
+    fn selected_range(&self) -> Option<RangeInclusive<usize>> {
+        let (anchor, cursor) = self.selection?;
+        let end = anchor.max(cursor).min(self.bytes.len().checked_sub(1)?);
+        let start = anchor.min(cursor);
+        (start <= end).then_some(start..=end)
+    }
+
+    /// The selected bytes, or all of them when nothing is selected.
+    fn selected(&self) -> &[u8] {
+        match self.selected_range() {
+            Some(range) => &self.bytes[range],
+            None => &self.bytes,
+        }
+    }
+
+    fn cell_rect(&self, rect: Rect, index: usize, char_width: f32, row_height: f32) -> Rect {
+        let column = index % BYTES_PER_ROW;
+        let top = rect.top() + (index / BYTES_PER_ROW) as f32 * row_height;
+        let left = rect.left() + hex_column(column) as f32 * char_width;
+        Rect::from_min_size(Pos2::new(left, top), vec2(2.0 * char_width, row_height))
+    }
+
+    fn paint_hex(
+        &self,
+        ui: &Ui,
+        response: &Response,
+        rect: Rect,
+        font_id: &FontId,
+        char_width: f32,
+        row_height: f32,
+    ) {
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals();
+        let clip = ui.clip_rect();
+        let first_row = ((clip.top() - rect.top()) / row_height).max(0.0) as usize;
+        let last_row = ((clip.bottom() - rect.top()) / row_height).max(0.0) as usize;
+        let cursor = self.selection.map(|(_, cursor)| cursor);
+
+        // selection and cursor behind the text, in both columns
+        let cells = |index: usize| {
+            let hex = self.cell_rect(rect, index, char_width, row_height);
+            let column = index % BYTES_PER_ROW;
+            let ascii = Rect::from_min_size(
+                Pos2::new(
+                    rect.left() + (ASCII_COLUMN + column) as f32 * char_width,
+                    hex.top(),
+                ),
+                vec2(char_width, row_height),
+            );
+            [hex, ascii]
+        };
+        if let Some(range) = self.selected_range() {
+            let start = (*range.start()).max(first_row * BYTES_PER_ROW);
+            let end = (*range.end()).min((last_row + 1) * BYTES_PER_ROW);
+            for index in start..=end {
+                for cell in cells(index) {
+                    painter.rect_filled(cell, 0.0, visuals.selection.bg_fill);
+                }
+            }
+        }
+        if let (Some(cursor), true) = (cursor, response.has_focus()) {
+            for cell in cells(cursor) {
+                painter.rect_stroke(cell, 0.0, visuals.selection.stroke);
+            }
+        }
+
+        let text_color = visuals.text_color();
+        let weak_color = visuals.weak_text_color();
+        let rows = self.bytes.chunks(BYTES_PER_ROW).enumerate();
+        for (row, chunk) in rows.skip(first_row).take(last_row + 1 - first_row) {
+            let top = rect.top() + row as f32 * row_height;
+            let mut hex = String::with_capacity(ROW_CHARS);
+            for (column, byte) in chunk.iter().enumerate() {
+                let index = row * BYTES_PER_ROW + column;
+                match self.nibble.filter(|_| cursor == Some(index)) {
+                    Some(high) => hex.push_str(&format!("{:x}_ ", high)),
+                    None => hex.push_str(&format!("{:02x} ", byte)),
+                }
+                if column == BYTES_PER_ROW / 2 - 1 {
+                    hex.push(' ');
+                }
+            }
+            let ascii: String = chunk
+                .iter()
+                .map(|&byte| match byte {
+                    0x20..=0x7e => byte as char,
+                    _ => '.',
+                })
+                .collect();
+
+            let at = |column: usize| Pos2::new(rect.left() + column as f32 * char_width, top);
+            let align = Align2::LEFT_TOP;
+            painter.text(
+                at(0),
+                align,
+                format!("{:08x}", row * BYTES_PER_ROW),
+                font_id.clone(),
+                weak_color,
+            );
+            painter.text(at(HEX_COLUMN), align, hex, font_id.clone(), text_color);
+            painter.text(at(ASCII_COLUMN), align, ascii, font_id.clone(), text_color);
+        }
+
+        // the append cell past the last byte
+        if let (Some(high), Some(cursor)) = (self.nibble, cursor) {
+            if cursor == self.bytes.len() {
+                let cell = self.cell_rect(rect, cursor, char_width, row_height);
+                painter.text(
+                    cell.min,
+                    Align2::LEFT_TOP,
+                    format!("{:x}_", high),
+                    font_id.clone(),
+                    text_color,
+                );
+            }
+        }
+    }
+}
+
+/// Buttons switching between the views of a buffer.
+pub fn view_switch(ui: &mut Ui, view: &mut View) {
+    ui.selectable_value(view, View::Text, "Text");
+    ui.selectable_value(view, View::Hex, "Hex");
+}
+
+/// Character column of the `column`th byte of a row.
+fn hex_column(column: usize) -> usize {
+    HEX_COLUMN + 3 * column + column / (BYTES_PER_ROW / 2)
+}
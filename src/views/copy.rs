@@ -0,0 +1,107 @@
+use base64::{engine::general_purpose, Engine};
+use eframe::egui::Ui;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter};
+
+/// Ways to put bytes on the clipboard.
+#[derive(EnumIter, Display, PartialEq, Eq, Clone, Copy)]
+pub enum CopyFormat {
+    #[strum(to_string = "Hex")]
+    Hex,
+    #[strum(to_string = "Base64")]
+    Base64,
+    #[strum(to_string = "C array")]
+    CArray,
+    #[strum(to_string = "Rust &[u8]")]
+    Rust,
+    #[strum(to_string = "Python bytes")]
+    Python,
+    #[strum(to_string = "Go []byte")]
+    Go,
+}
+
+/// Bytes per line of the array formats.
+const BYTES_PER_LINE: usize = 12;
+
+impl CopyFormat {
+    pub fn format(self, bytes: &[u8]) -> String {
+        match self {
+            CopyFormat::Hex => hex::encode(bytes),
+            CopyFormat::Base64 => general_purpose::STANDARD.encode(bytes),
+            // C before C23 has neither zero-length arrays nor empty
+            // initializers, so there is no array to declare
+            CopyFormat::CArray if bytes.is_empty() => {
+                "const unsigned char *data = NULL;\nsize_t data_len = 0;".to_string()
+            }
+            CopyFormat::CArray => format!(
+                "unsigned char data[{}] = {{{}}};",
+                bytes.len(),
+                byte_list(bytes)
+            ),
+            CopyFormat::Rust => format!("&[{}]", byte_list(bytes)),
+            CopyFormat::Python => {
+                let mut literal = String::from("b'");
+                for &byte in bytes {
+                    match byte {
+                        b'\\' => literal.push_str("\\\\"),
+                        b'\'' => literal.push_str("\\'"),
+                        b'\t' => literal.push_str("\\t"),
+                        b'\n' => literal.push_str("\\n"),
+                        b'\r' => literal.push_str("\\r"),
+                        0x20..=0x7e => literal.push(byte as char),
+                        _ => literal.push_str(&format!("\\x{:02x}", byte)),
+                    }
+                }
+                literal.push('\'');
+                literal
+            }
+            CopyFormat::Go => format!("[]byte{{{}}}", byte_list(bytes)),
+        }
+    }
+}
+
+/// `0x48, 0x65, ...`, wrapped onto indented lines when it gets long.
+fn byte_list(bytes: &[u8]) -> String {
+    let lines: Vec<String> = bytes
+        .chunks(BYTES_PER_LINE)
+        .map(|line| {
+            line.iter()
+                .map(|byte| format!("0x{:02x}", byte))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .collect();
+    match lines.len() {
+        0 => String::new(),
+        1 => lines[0].clone(),
+        _ => format!("\n    {},\n", lines.join(",\n    ")),
+    }
+}
+
+/// One "Copy as ..." button per format; for use inside a menu.
+pub fn copy_menu(ui: &mut Ui, bytes: &[u8]) {
+    for format in CopyFormat::iter() {
+        if ui.button(format!("Copy as {}", format)).clicked() {
+            ui.close_menu();
+            ui.ctx().copy_text(format.format(bytes));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CopyFormat;
+
+    #[test]
+    fn c_array() {
+        assert_eq!(
+            CopyFormat::CArray.format(b"Hi"),
+            "unsigned char data[2] = {0x48, 0x69};"
+        );
+        // a length of 0, where `data[] = {0}` would hold one byte
+        assert_eq!(
+            CopyFormat::CArray.format(b""),
+            "const unsigned char *data = NULL;\nsize_t data_len = 0;"
+        );
+    }
+}
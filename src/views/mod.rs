@@ -1 +1,5 @@
-pub mod splitter;
\ No newline at end of file
+pub mod buffer;
+pub mod copy;
+pub mod module;
+pub mod search;
+pub mod splitter;
@@ -0,0 +1,206 @@
+use cybercook::{
+    modules::Module,
+    recipe::{Step, StepResult},
+};
+use eframe::egui::{Align, Color32, Frame, Id, Layout, Response, RichText, Sense, Ui};
+
+/// Something the user asked for from a recipe step's card.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepAction {
+    Duplicate,
+    RunTo,
+    /// An argument or switch of the step was changed.
+    Edited,
+}
+
+/// What the last bake says about a step.
+#[derive(Clone, Copy)]
+pub enum StepStatus<'a> {
+    /// Execution stopped before reaching the step.
+    NotRun,
+    /// Execution paused right before this step.
+    Paused,
+    Ran(&'a StepResult),
+}
+
+/// Module card for the module list.
+pub trait ModuleView {
+    /// `favourite` is toggled by the card's pin button.
+    fn render_list(&self, ui: &mut Ui, index: usize, favourite: &mut bool) -> Response;
+}
+
+/// Module card for the recipe column.
+pub trait StepView {
+    /// `run_to` tells whether "run up to here" is set on this step.
+    fn render(
+        &mut self,
+        ui: &mut Ui,
+        index: usize,
+        status: StepStatus,
+        run_to: bool,
+    ) -> Option<StepAction>;
+}
+
+impl StepView for Step {
+    fn render(
+        &mut self,
+        ui: &mut Ui,
+        index: usize,
+        status: StepStatus,
+        run_to: bool,
+    ) -> Option<StepAction> {
+        let mut action = None;
+        let mut edited = false;
+        let failed = matches!(status, StepStatus::Ran(StepResult::Failed(_)));
+        let background_color = if failed {
+            if ui.visuals().dark_mode {
+                Color32::from_rgb(90, 40, 40)
+            } else {
+                Color32::from_rgb(250, 195, 195)
+            }
+        } else if ui.visuals().dark_mode {
+            if index.is_multiple_of(2) {
+                Color32::from_rgb(40, 60, 60)
+            } else {
+                Color32::from_rgb(30, 50, 50)
+            }
+        } else if index.is_multiple_of(2) {
+            Color32::from_rgb(240, 220, 220)
+        } else {
+            Color32::from_rgb(230, 210, 210)
+        };
+
+        Frame::none().fill(background_color).show(ui, |ui| {
+            ui.group(|ui| {
+                ui.set_min_height(32.0);
+                ui.set_min_width(ui.available_width());
+                ui.vertical(|ui| {
+                    if let StepStatus::Paused = status {
+                        ui.colored_label(ui.visuals().warn_fg_color, "⏸ Paused here");
+                    }
+                    ui.horizontal(|ui| {
+                        // set drag detection area
+                        let resp = ui
+                            .horizontal(|ui| {
+                                ui.heading(self.module.name());
+                                ui.label(self.module.description());
+                            })
+                            .response;
+                        ui.interact(resp.rect, Id::new(self.module.id()), Sense::drag());
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            if ui.small_button("🗐").on_hover_text("Duplicate").clicked() {
+                                action = Some(StepAction::Duplicate);
+                            }
+                            if ui
+                                .selectable_label(run_to, "⏭")
+                                .on_hover_text("Run up to here")
+                                .clicked()
+                            {
+                                action = Some(StepAction::RunTo);
+                            }
+                            let breakpoint = ui
+                                .toggle_value(&mut self.breakpoint, "⏺")
+                                .on_hover_text("Pause before this step");
+                            let enabled = ui
+                                .checkbox(&mut self.enabled, "")
+                                .on_hover_text("Enable or disable this step");
+                            edited |= breakpoint.changed() || enabled.changed();
+                        });
+                    });
+                    edited |= self.module.render_inner(ui);
+                    match status {
+                        StepStatus::Ran(StepResult::Done(output)) => {
+                            ui.separator();
+                            ui.label(
+                                RichText::new(format!(
+                                    "{} bytes  {}",
+                                    output.len(),
+                                    preview(output)
+                                ))
+                                .monospace()
+                                .weak(),
+                            );
+                        }
+                        StepStatus::Ran(StepResult::Skipped) => {
+                            ui.separator();
+                            ui.label(RichText::new("Disabled, input passed through").weak());
+                        }
+                        StepStatus::Ran(StepResult::Failed(e)) => {
+                            ui.separator();
+                            ui.colored_label(ui.visuals().error_fg_color, format!("{:#}", e));
+                        }
+                        StepStatus::NotRun | StepStatus::Paused => {}
+                    }
+                });
+            });
+        });
+
+        action.or(edited.then_some(StepAction::Edited))
+    }
+}
+
+impl ModuleView for dyn Module {
+    fn render_list(&self, ui: &mut Ui, index: usize, favourite: &mut bool) -> Response {
+        // 使用交替背景颜色
+        // light theme: 240, 220
+        // dark theme: 40, 60
+
+        let background_color = if ui.visuals().dark_mode {
+            if index.is_multiple_of(2) {
+                Color32::from_rgb(40, 60, 60)
+            } else {
+                Color32::from_rgb(30, 50, 50)
+            }
+        } else if index.is_multiple_of(2) {
+            Color32::from_rgb(240, 220, 220)
+        } else {
+            Color32::from_rgb(230, 210, 210)
+        };
+
+        let frame = Frame::none().fill(background_color).show(ui, |ui| {
+            ui.group(|ui| {
+                ui.set_min_height(32.0);
+                ui.set_min_width(ui.available_width());
+                ui.vertical(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.heading(self.name());
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            ui.toggle_value(favourite, "★")
+                                .on_hover_text("Pin to favourites");
+                        });
+                    });
+                    ui.label(self.description());
+                });
+            });
+        });
+
+        frame.response
+    }
+}
+
+/// A one-line glimpse of a step's output: the text when it is printable
+/// UTF-8, hex otherwise.
+fn preview(data: &[u8]) -> String {
+    const LIMIT: usize = 48;
+    match std::str::from_utf8(data) {
+        Ok(text) if !text.chars().any(|c| c.is_control() && !c.is_whitespace()) => {
+            let mut preview = text
+                .chars()
+                .take(LIMIT)
+                .collect::<String>()
+                .escape_debug()
+                .to_string();
+            if text.chars().count() > LIMIT {
+                preview.push('…');
+            }
+            preview
+        }
+        _ => {
+            let mut preview = hex::encode(&data[..data.len().min(LIMIT / 2)]);
+            if data.len() > LIMIT / 2 {
+                preview.push('…');
+            }
+            preview
+        }
+    }
+}
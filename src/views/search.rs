@@ -0,0 +1,32 @@
+/// Scores `text` against `query` typed as a subsequence, ignoring case and
+/// whitespace in the query. Higher is better; `None` if it does not match.
+/// Consecutive characters and characters starting a word score extra.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let mut query = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut previous_matched = false;
+    for c in text.chars().flat_map(char::to_lowercase) {
+        let Some(&wanted) = query.peek() else {
+            break;
+        };
+        let matched = c == wanted;
+        if matched {
+            query.next();
+            score += 1;
+            if previous_matched {
+                score += 2;
+            }
+            if previous.is_none_or(|previous| !previous.is_alphanumeric()) {
+                score += 3;
+            }
+        }
+        previous_matched = matched;
+        previous = Some(c);
+    }
+    query.peek().is_none().then_some(score)
+}
@@ -1,8 +1,9 @@
 // Slightly modifed, original:
 // https://gist.github.com/mkalte666/f9a982be0ac0276080d3434ab9ea4655
+#![allow(dead_code)]
 
+use eframe::egui::{CursorIcon, Id, Layout, Pos2, Rect, Rounding, Sense, Ui, UiBuilder, Vec2};
 use std::hash::Hash;
-use eframe::egui::{CursorIcon, Id, Layout, Pos2, Rect, Rounding, Sense, Ui, Vec2};
 
 /// An axis that a Splitter can use
 #[derive(Copy, Clone, Debug)]
@@ -26,7 +27,6 @@ pub struct Splitter {
     data: SplitterData,
 }
 impl Splitter {
-
     /// Create a new Splitter
     pub fn new(id_source: impl Hash, axis: SplitterAxis) -> Self {
         Self {
@@ -115,7 +115,11 @@ impl Splitter {
         };
 
         let child_rect_a = Rect::from_min_size(ui.next_widget_position(), child_size_a);
-        let mut ui_a = ui.child_ui(child_rect_a, Layout::default(), None);
+        let mut ui_a = ui.new_child(
+            UiBuilder::new()
+                .max_rect(child_rect_a)
+                .layout(Layout::default()),
+        );
         ui_a.set_clip_rect(child_rect_a);
 
         let sep_rect = match data.axis {
@@ -161,7 +165,11 @@ impl Splitter {
                 Rect::from_min_size(Pos2::new(sep_rect.min.x, sep_rect.max.y), child_size_b)
             }
         };
-        let mut ui_b = ui.child_ui(child_rect_b, Layout::default(), None);
+        let mut ui_b = ui.new_child(
+            UiBuilder::new()
+                .max_rect(child_rect_b)
+                .layout(Layout::default()),
+        );
         ui_b.set_clip_rect(child_rect_b);
 
         add_contents(&mut ui_a, &mut ui_b);
@@ -191,4 +199,4 @@ impl Splitter {
             mem.data.insert_temp(self.id, data);
         })
     }
-}
\ No newline at end of file
+}
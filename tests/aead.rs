@@ -0,0 +1,286 @@
+//! Known-answer tests for the AEAD modules: AES-GCM from the GCM
+//! specification (McGrew and Viega, test cases 2 to 4), AES-CCM from
+//! RFC 3610 and SP 800-38C, EAX from the EAX paper (Bellare, Rogaway and
+//! Wagner) and AES-GCM-SIV from RFC 8452. The modules lay output out as
+//! `ciphertext || tag`.
+
+mod common;
+
+use common::{module, unhex};
+use cybercook::modules::args;
+
+/// Seals `plaintext`, compares with `sealed`, then opens it again.
+fn check_mode(
+    mode: &str,
+    tag_length: &str,
+    [key, nonce, aad]: [&str; 3],
+    plaintext: &str,
+    sealed: &str,
+) {
+    let args = [
+        ("mode", mode),
+        ("tag_length", tag_length),
+        ("key", &format!("hex:{}", key)),
+        ("nonce", &format!("hex:{}", nonce)),
+        ("aad", &format!("hex:{}", aad)),
+    ];
+    let encrypted = module("aead-encrypt", &args)
+        .process(&unhex(plaintext))
+        .unwrap();
+    assert_eq!(hex::encode(encrypted), sealed);
+    let decrypted = module("aead-decrypt", &args)
+        .process(&unhex(sealed))
+        .unwrap();
+    assert_eq!(hex::encode(decrypted), plaintext);
+}
+
+fn check(key: &str, nonce: &str, aad: &str, plaintext: &str, sealed: &str) {
+    check_mode("GCM", "16", [key, nonce, aad], plaintext, sealed);
+}
+
+const KEY: &str = "feffe9928665731c6d6a8f9467308308";
+const NONCE: &str = "cafebabefacedbaddecaf888";
+const PLAINTEXT: &str = "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+                         1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b391aafd255";
+
+#[test]
+fn gcm_zero_block() {
+    check(
+        "00000000000000000000000000000000",
+        "000000000000000000000000",
+        "",
+        "00000000000000000000000000000000",
+        "0388dace60b6a392f328c2b971b2fe78ab6e47d42cec13bdf53a67b21257bddf",
+    );
+}
+
+#[test]
+fn gcm() {
+    check(
+        KEY,
+        NONCE,
+        "",
+        PLAINTEXT,
+        "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e\
+         21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091473f5985\
+         4d5c2af327cd64a62cf35abd2ba6fab4",
+    );
+}
+
+#[test]
+fn gcm_aad() {
+    check(
+        KEY,
+        NONCE,
+        "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+        &PLAINTEXT[..120],
+        "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e\
+         21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091\
+         5bc94fbc3221a5db94fae95ae7121a47",
+    );
+}
+
+#[test]
+fn gcm_tampered_tag() {
+    let args = [
+        ("mode", "GCM"),
+        ("key", "hex:00000000000000000000000000000000"),
+        ("nonce", "hex:000000000000000000000000"),
+    ];
+    let error = module("aead-decrypt", &args)
+        .process(&unhex(
+            "0388dace60b6a392f328c2b971b2fe78ab6e47d42cec13bdf53a67b21257bdde",
+        ))
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Authentication failed: the tag does not match"
+    );
+}
+
+/// RFC 3610 packet vector #1 and SP 800-38C appendix C examples 1 and 2.
+#[test]
+fn ccm() {
+    check_mode(
+        "CCM",
+        "8",
+        [
+            "c0c1c2c3c4c5c6c7c8c9cacbcccdcecf",
+            "00000003020100a0a1a2a3a4a5",
+            "0001020304050607",
+        ],
+        "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e",
+        "588c979a61c663d2f066d0c2c0f989806d5f6b61dac38417e8d12cfdf926e0",
+    );
+    check_mode(
+        "CCM",
+        "4",
+        [
+            "404142434445464748494a4b4c4d4e4f",
+            "10111213141516",
+            "0001020304050607",
+        ],
+        "20212223",
+        "7162015b4dac255d",
+    );
+    check_mode(
+        "CCM",
+        "6",
+        [
+            "404142434445464748494a4b4c4d4e4f",
+            "1011121314151617",
+            "000102030405060708090a0b0c0d0e0f",
+        ],
+        "202122232425262728292a2b2c2d2e2f",
+        "d2a1f0e051ea5f62081a7792073d593d1fc64fbfaccd",
+    );
+}
+
+/// The first three vectors of the EAX paper.
+#[test]
+fn eax() {
+    check_mode(
+        "EAX",
+        "16",
+        [
+            "233952dee4d5ed5f9b9c6d6ff80ff478",
+            "62ec67f9c3a4a407fcb2a8c49031a8b3",
+            "6bfb914fd07eae6b",
+        ],
+        "",
+        "e037830e8389f27b025a2d6527e79d01",
+    );
+    check_mode(
+        "EAX",
+        "16",
+        [
+            "91945d3f4dcbee0bf45ef52255f095a4",
+            "becaf043b0a23d843194ba972c66debd",
+            "fa3bfd4806eb53fa",
+        ],
+        "f7fb",
+        "19dd5c4c9331049d0bdab0277408f67967e5",
+    );
+    check_mode(
+        "EAX",
+        "16",
+        [
+            "01f74ad64077f2e704c0f60ada3dd523",
+            "70c3db4f0d26368400a10ed05d2bff5e",
+            "234a3463c1264ac6",
+        ],
+        "1a47cb4933",
+        "d851d5bae03a59f238a23e39199dc9266626c40f80",
+    );
+}
+
+/// RFC 8452 appendix C.1 and C.2.
+#[test]
+fn gcm_siv() {
+    let key_128 = "01000000000000000000000000000000";
+    let nonce = "030000000000000000000000";
+    check_mode(
+        "GCM-SIV",
+        "16",
+        [key_128, nonce, ""],
+        "",
+        "dc20e2d83f25705bb49e439eca56de25",
+    );
+    check_mode(
+        "GCM-SIV",
+        "16",
+        [key_128, nonce, ""],
+        "0100000000000000",
+        "b5d839330ac7b786578782fff6013b815b287c22493a364c",
+    );
+    check_mode(
+        "GCM-SIV",
+        "16",
+        [key_128, nonce, "01"],
+        "0200000000000000",
+        "1e6daba35669f4273b0a1a2560969cdf790d99759abd1508",
+    );
+    check_mode(
+        "GCM-SIV",
+        "16",
+        [
+            "0100000000000000000000000000000000000000000000000000000000000000",
+            nonce,
+            "",
+        ],
+        "0100000000000000",
+        "c2ef328e5c71c83b843122130f7364b761e0b97427e3df28",
+    );
+}
+
+/// With `verify=false` a forged tag is discarded and the plaintext still
+/// comes back, except for GCM-SIV, whose tag is also its counter block.
+#[test]
+fn unverified() {
+    let cases = [
+        ("GCM", "16", "000000000000000000000000"),
+        ("CCM", "8", "00000003020100a0a1a2a3a4a5"),
+        ("EAX", "16", "62ec67f9c3a4a407fcb2a8c49031a8b3"),
+    ];
+    let plaintext = b"attack at dawn";
+    for (mode, tag_length, nonce) in cases {
+        let mut args = vec![
+            ("mode", mode),
+            ("tag_length", tag_length),
+            ("key", "hex:000102030405060708090a0b0c0d0e0f"),
+        ];
+        let nonce = format!("hex:{}", nonce);
+        args.push(("nonce", &nonce));
+        let mut sealed = module("aead-encrypt", &args).process(plaintext).unwrap();
+        *sealed.last_mut().unwrap() ^= 1;
+        assert!(module("aead-decrypt", &args).process(&sealed).is_err());
+        args.push(("verify", "false"));
+        let opened = module("aead-decrypt", &args).process(&sealed).unwrap();
+        assert_eq!(opened, plaintext, "{}", mode);
+    }
+
+    // the wrong AAD is not noticed either
+    let args = [
+        ("mode", "GCM-SIV"),
+        ("key", "hex:01000000000000000000000000000000"),
+        ("nonce", "hex:030000000000000000000000"),
+        ("aad", "hex:ff"),
+        ("verify", "false"),
+    ];
+    let opened = module("aead-decrypt", &args)
+        .process(&unhex("1e6daba35669f4273b0a1a2560969cdf790d99759abd1508"))
+        .unwrap();
+    assert_eq!(hex::encode(opened), "0200000000000000");
+}
+
+/// Tag lengths a mode does not support are refused rather than reaching
+/// the cipher.
+#[test]
+fn unsupported_tag_length() {
+    let mut decrypt = module("aead-decrypt", &[("mode", "GCM-SIV"), ("verify", "false")]);
+    let error = args::set(decrypt.as_mut(), "tag_length", "4").unwrap_err();
+    assert_eq!(
+        format!("{:#}", error),
+        "argument \"tag_length\": 4 is not allowed, expected one of 16"
+    );
+}
+
+/// A tag length chosen for one mode must not survive a switch to a mode
+/// that does not support it, as `bake r.json --arg mode=...` does.
+#[test]
+fn tag_length_follows_mode() {
+    let mut decrypt = module(
+        "aead-decrypt",
+        &[
+            ("mode", "CCM"),
+            ("tag_length", "4"),
+            ("verify", "false"),
+            ("key", "hex:00000000000000000000000000000000"),
+            ("nonce", "hex:000000000000000000000000"),
+        ],
+    );
+    assert!(args::set(decrypt.as_mut(), "mode", "GCM-SIV").unwrap());
+    assert_eq!(decrypt.export_args().unwrap()["tag_length"], 16);
+    let error = decrypt.process(&[0; 8]).unwrap_err();
+    assert_eq!(error.to_string(), "Input is shorter than the tag");
+}
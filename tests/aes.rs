@@ -0,0 +1,264 @@
+//! NIST SP 800-38A known-answer tests (appendix F) for every AES mode and
+//! key size, and known-answer tests for each padding.
+
+mod common;
+
+use common::{module, unhex};
+
+const PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51\
+                         30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710";
+
+const KEY_128: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+const KEY_192: &str = "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b";
+const KEY_256: &str = "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4";
+
+const IV: &str = "000102030405060708090a0b0c0d0e0f";
+/// Initial counter block of the CTR vectors.
+const COUNTER: &str = "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff";
+
+/// Encrypts and decrypts the four-block SP 800-38A plaintext. The vectors
+/// are unpadded, so the block modes use their `NoPadding` variants.
+fn check(mode: &str, key: &str, iv: &str, ciphertext: &str) {
+    let args = [
+        ("mode", mode),
+        ("key", &format!("hex:{}", key)),
+        ("iv", &format!("hex:{}", iv)),
+    ];
+    let encrypted = module("aes-encrypt", &args)
+        .process(&unhex(PLAINTEXT))
+        .unwrap();
+    assert_eq!(hex::encode(encrypted), ciphertext, "{} encrypt", mode);
+    let decrypted = module("aes-decrypt", &args)
+        .process(&unhex(ciphertext))
+        .unwrap();
+    assert_eq!(hex::encode(decrypted), PLAINTEXT, "{} decrypt", mode);
+}
+
+#[test]
+fn ecb() {
+    check(
+        "ECB/NoPadding",
+        KEY_128,
+        "",
+        "3ad77bb40d7a3660a89ecaf32466ef97f5d3d58503b9699de785895a96fdbaaf\
+         43b1cd7f598ece23881b00e3ed0306887b0c785e27e8ad3f8223207104725dd4",
+    );
+    check(
+        "ECB/NoPadding",
+        KEY_192,
+        "",
+        "bd334f1d6e45f25ff712a214571fa5cc974104846d0ad3ad7734ecb3ecee4eef\
+         ef7afd2270e2e60adce0ba2face6444e9a4b41ba738d6c72fb16691603c18e0e",
+    );
+    check(
+        "ECB/NoPadding",
+        KEY_256,
+        "",
+        "f3eed1bdb5d2a03c064b5a7e3db181f8591ccb10d410ed26dc5ba74a31362870\
+         b6ed21b99ca6f4f9f153e7b1beafed1d23304b7a39f9f3ff067d8d8f9e24ecc7",
+    );
+}
+
+#[test]
+fn cbc() {
+    check(
+        "CBC/NoPadding",
+        KEY_128,
+        IV,
+        "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2\
+         73bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7",
+    );
+    check(
+        "CBC/NoPadding",
+        KEY_192,
+        IV,
+        "4f021db243bc633d7178183a9fa071e8b4d9ada9ad7dedf4e5e738763f69145a\
+         571b242012fb7ae07fa9baac3df102e008b0e27988598881d920a9e64f5615cd",
+    );
+    check(
+        "CBC/NoPadding",
+        KEY_256,
+        IV,
+        "f58c4c04d6e5f1ba779eabfb5f7bfbd69cfc4e967edb808d679f777bc6702c7d\
+         39f23369a9d9bacfa530e26304231461b2eb05e2c39be9fcda6c19078c6a9d1b",
+    );
+}
+
+#[test]
+fn cfb128() {
+    check(
+        "CFB",
+        KEY_128,
+        IV,
+        "3b3fd92eb72dad20333449f8e83cfb4ac8a64537a0b3a93fcde3cdad9f1ce58b\
+         26751f67a3cbb140b1808cf187a4f4dfc04b05357c5d1c0eeac4c66f9ff7f2e6",
+    );
+    check(
+        "CFB",
+        KEY_192,
+        IV,
+        "cdc80d6fddf18cab34c25909c99a417467ce7f7f81173621961a2b70171d3d7a\
+         2e1e8a1dd59b88b1c8e60fed1efac4c9c05f9f9ca9834fa042ae8fba584b09ff",
+    );
+    check(
+        "CFB",
+        KEY_256,
+        IV,
+        "dc7e84bfda79164b7ecd8486985d386039ffed143b28b1c832113c6331e5407b\
+         df10132415e54b92a13ed0a8267ae2f975a385741ab9cef82031623d55b1e471",
+    );
+}
+
+#[test]
+fn ofb() {
+    check(
+        "OFB",
+        KEY_128,
+        IV,
+        "3b3fd92eb72dad20333449f8e83cfb4a7789508d16918f03f53c52dac54ed825\
+         9740051e9c5fecf64344f7a82260edcc304c6528f659c77866a510d9c1d6ae5e",
+    );
+    check(
+        "OFB",
+        KEY_192,
+        IV,
+        "cdc80d6fddf18cab34c25909c99a4174fcc28b8d4c63837c09e81700c1100401\
+         8d9a9aeac0f6596f559c6d4daf59a5f26d9f200857ca6c3e9cac524bd9acc92a",
+    );
+    check(
+        "OFB",
+        KEY_256,
+        IV,
+        "dc7e84bfda79164b7ecd8486985d38604febdc6740d20b3ac88f6ad82a4fb08d\
+         71ab47a086e86eedf39d1c5bba97c4080126141d67f37be8538f5a8be740e484",
+    );
+}
+
+#[test]
+fn ctr() {
+    check(
+        "CTR",
+        KEY_128,
+        COUNTER,
+        "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff\
+         5ae4df3edbd5d35e5b4f09020db03eab1e031dda2fbe03d1792170a0f3009cee",
+    );
+    check(
+        "CTR",
+        KEY_192,
+        COUNTER,
+        "1abc932417521ca24f2b0459fe7e6e0b090339ec0aa6faefd5ccc2c6f4ce8e94\
+         1e36b26bd1ebc670d1bd1d665620abf74f78a7f6d29809585a97daec58c6b050",
+    );
+    check(
+        "CTR",
+        KEY_256,
+        COUNTER,
+        "601ec313775789a5b7a7f504bbf3d228f443e3ca4d62b59aca84e990cacaf5c5\
+         2b0930daa23de94ce87017ba2d84988ddfc9c58db67aada613c2dd08457941a6",
+    );
+}
+
+/// PKCS#7 adds a full block to block-aligned input.
+#[test]
+fn cbc_pkcs7() {
+    let args = [
+        ("mode", "CBC"),
+        ("key", &format!("hex:{}", KEY_128)),
+        ("iv", &format!("hex:{}", IV)),
+    ];
+    let encrypted = module("aes-encrypt", &args)
+        .process(&unhex(PLAINTEXT))
+        .unwrap();
+    assert_eq!(encrypted.len(), 80);
+    assert_eq!(
+        hex::encode(&encrypted[..64]),
+        "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2\
+         73bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7"
+    );
+}
+
+/// A 12-byte block padded to 16, encrypted with ECB and the first
+/// SP 800-38A key.
+#[test]
+fn paddings() {
+    let plaintext = unhex("6bc1bee22e409f96e93d7e11");
+    for (padding, ciphertext) in [
+        ("Zero", "80c8dff80e4c3e4593d95e33c61a3e05"),
+        ("ANSI X.923", "fb1bf69ae9bca6ff1c8b2d2100b5de2f"),
+        ("ISO/IEC 7816-4", "10612e6d7d74c3acf05c7bc5875e08cd"),
+    ] {
+        let args = [
+            ("mode", "ECB"),
+            ("key", &format!("hex:{}", KEY_128)),
+            ("padding", padding),
+        ];
+        let encrypted = module("aes-encrypt", &args).process(&plaintext).unwrap();
+        assert_eq!(hex::encode(encrypted), ciphertext, "{} encrypt", padding);
+        let decrypted = module("aes-decrypt", &args)
+            .process(&unhex(ciphertext))
+            .unwrap();
+        assert_eq!(decrypted, plaintext, "{} decrypt", padding);
+    }
+}
+
+/// ISO 10126 pads with random bytes, so only the length byte is known.
+#[test]
+fn iso10126_padding() {
+    let plaintext = unhex("6bc1bee22e409f96e93d7e11");
+    let key = format!("hex:{}", KEY_128);
+    let args = [("mode", "ECB"), ("key", &key), ("padding", "ISO 10126")];
+    let encrypted = module("aes-encrypt", &args).process(&plaintext).unwrap();
+    assert_eq!(encrypted.len(), 16);
+    let block = module("aes-decrypt", &[("mode", "ECB/NoPadding"), ("key", &key)])
+        .process(&encrypted)
+        .unwrap();
+    assert_eq!(block[..12], plaintext);
+    assert_eq!(block[15], 4);
+    let decrypted = module("aes-decrypt", &args).process(&encrypted).unwrap();
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn unaligned_lengths() {
+    let key = format!("hex:{}", KEY_128);
+    let args = [("mode", "ECB"), ("key", &key), ("padding", "None")];
+    let error = module("aes-encrypt", &args).process(&[0; 12]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Input length 12 is not a multiple of the block size (16), select a padding"
+    );
+    let error = module("aes-decrypt", &[("mode", "ECB"), ("key", &key)])
+        .process(&[0; 20])
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Ciphertext length 20 is not a multiple of the block size (16)"
+    );
+}
+
+#[test]
+fn invalid_lengths() {
+    let error = module("aes-encrypt", &[("key", "short"), ("iv", "hex:00")])
+        .process(b"data")
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Key: 5 bytes, AES needs 16, 24 or 32");
+    let key = format!("hex:{}", KEY_128);
+    let error = module("aes-encrypt", &[("key", &key), ("iv", "hex:00")])
+        .process(b"data")
+        .unwrap_err();
+    assert_eq!(error.to_string(), "IV: 1 bytes, CBC needs 16");
+}
+
+/// Notes are rebuilt on every frame, so file arguments are only read when
+/// baking.
+#[test]
+fn notes_skip_files() {
+    let path = std::env::temp_dir().join("cybercook-missing-key");
+    let key = format!("file:{}", path.display());
+    let encrypt = module("aes-encrypt", &[("key", &key), ("iv", "hex:00")]);
+    let notes: Vec<_> = encrypt.notes().iter().map(|note| note.arg).collect();
+    assert_eq!(notes, ["iv"]);
+    let error = encrypt.process(b"data").unwrap_err();
+    assert!(error.to_string().starts_with("Key: reading "));
+}
@@ -0,0 +1,115 @@
+//! RFC 4648 section 10 test vectors.
+
+mod common;
+
+use common::module;
+
+const VECTORS: &[(&str, &str)] = &[
+    ("", ""),
+    ("f", "Zg=="),
+    ("fo", "Zm8="),
+    ("foo", "Zm9v"),
+    ("foob", "Zm9vYg=="),
+    ("fooba", "Zm9vYmE="),
+    ("foobar", "Zm9vYmFy"),
+];
+
+#[test]
+fn encode() {
+    let encoder = module("base64-encode", &[]);
+    for (plain, encoded) in VECTORS {
+        assert_eq!(
+            encoder.process(plain.as_bytes()).unwrap(),
+            encoded.as_bytes()
+        );
+    }
+}
+
+#[test]
+fn decode() {
+    let decoder = module("base64-decode", &[]);
+    for (plain, encoded) in VECTORS {
+        assert_eq!(
+            decoder.process(encoded.as_bytes()).unwrap(),
+            plain.as_bytes()
+        );
+    }
+}
+
+#[test]
+fn url_safe_unpadded() {
+    let args = [("alphabet", "URL-safe (-_)"), ("padding", "false")];
+    let encoded = module("base64-encode", &args)
+        .process(&[0xfb, 0xff, 0xbf, 0xfe])
+        .unwrap();
+    assert_eq!(encoded, b"-_-__g");
+    let decoded = module("base64-decode", &args).process(b"-_-__g").unwrap();
+    assert_eq!(decoded, [0xfb, 0xff, 0xbf, 0xfe]);
+}
+
+#[test]
+fn mime_line_breaks() {
+    let input: Vec<u8> = (0..64).collect();
+    let encoded = module("base64-encode", &[("wrap", "true")])
+        .process(&input)
+        .unwrap();
+    assert_eq!(
+        encoded,
+        &b"AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4vMDEyMzQ1Njc4\r\n\
+           OTo7PD0+Pw=="[..]
+    );
+    let decoded = module("base64-decode", &[("lenient", "true")])
+        .process(&encoded)
+        .unwrap();
+    assert_eq!(decoded, input);
+}
+
+/// The strict decoder takes only what the encoder writes.
+#[test]
+fn strict() {
+    let decoder = module("base64-decode", &[]);
+    for invalid in [
+        &b"Zm9v!YmFy"[..],
+        b"Zm9v\nYmFy",
+        b"Zm9vYg\n",
+        b"Zm9vYg",
+        b"Zm9vYg=",
+    ] {
+        assert!(decoder.process(invalid).is_err(), "{:?}", invalid);
+    }
+    let unpadded = module("base64-decode", &[("padding", "false")]);
+    assert_eq!(unpadded.process(b"Zm9vYg").unwrap(), b"foob");
+    assert!(unpadded.process(b"Zm9vYg==").is_err());
+}
+
+#[test]
+fn lenient() {
+    let decoder = module("base64-decode", &[("lenient", "true")]);
+    assert_eq!(decoder.process(b" Zm9v\nYm*Fy!\t").unwrap(), b"foobar");
+    assert_eq!(decoder.process(b"Zm9vYg").unwrap(), b"foob");
+}
+
+/// The standard alphabet reversed.
+#[test]
+fn custom_alphabet() {
+    let args = [
+        ("alphabet", "Custom"),
+        (
+            "custom",
+            "/+9876543210zyxwvutsrqponmlkjihgfedcbaZYXWVUTSRQPONMLKJIHGFEDCBA",
+        ),
+    ];
+    let encoded = module("base64-encode", &args)
+        .process(b"Hello, CyberCook!")
+        .unwrap();
+    assert_eq!(encoded, b"t5qTk5DT37yGnZqNvJCQlN7=");
+    let decoded = module("base64-decode", &args).process(&encoded).unwrap();
+    assert_eq!(decoded, b"Hello, CyberCook!");
+}
+
+#[test]
+fn invalid_custom_alphabet() {
+    let args = [("alphabet", "Custom"), ("custom", "abc")];
+    let error = module("base64-encode", &args).process(b"x").unwrap_err();
+    assert!(error.to_string().starts_with("Characters:"), "{}", error);
+}
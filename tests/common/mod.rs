@@ -0,0 +1,22 @@
+//! Helpers shared by the integration tests. Each test crate uses a subset.
+#![allow(dead_code)]
+
+use cybercook::modules::{self, args, Module};
+
+/// The module registered under `key`, configured like `bake -a NAME=VALUE`.
+pub fn module(key: &str, args: &[(&str, &str)]) -> Box<dyn Module> {
+    let mut module = modules::create(key).unwrap_or_else(|| panic!("no module {}", key));
+    for (name, value) in args {
+        assert!(
+            args::set(module.as_mut(), name, value).unwrap(),
+            "{} has no argument {}",
+            key,
+            name
+        );
+    }
+    module
+}
+
+pub fn unhex(value: &str) -> Vec<u8> {
+    hex::decode(value).unwrap()
+}
@@ -0,0 +1,187 @@
+//! Known-answer tests for the Base32, Base45, Base58, Base62, Base85 and
+//! Base91 codecs, from their specifications where there is one.
+
+mod common;
+
+use common::{module, unhex};
+
+/// Encodes `plain` with the encoder for `codec`, then decodes it again.
+fn check(codec: &str, args: &[(&str, &str)], plain: &[u8], encoded: &str) {
+    let encoder = module(&format!("{}-encode", codec), args);
+    assert_eq!(
+        String::from_utf8(encoder.process(plain).unwrap()).unwrap(),
+        encoded
+    );
+    let decoder = module(&format!("{}-decode", codec), &args[..args.len().min(1)]);
+    assert_eq!(decoder.process(encoded.as_bytes()).unwrap(), plain);
+}
+
+/// RFC 4648 section 10.
+#[test]
+fn base32() {
+    for (plain, standard, hex) in [
+        ("", "", ""),
+        ("f", "MY======", "CO======"),
+        ("fo", "MZXQ====", "CPNG===="),
+        ("foo", "MZXW6===", "CPNMU==="),
+        ("foob", "MZXW6YQ=", "CPNMUOG="),
+        ("fooba", "MZXW6YTB", "CPNMUOJ1"),
+        ("foobar", "MZXW6YTBOI======", "CPNMUOJ1E8======"),
+    ] {
+        check("base32", &[], plain.as_bytes(), standard);
+        check(
+            "base32",
+            &[("variant", "RFC 4648 hex")],
+            plain.as_bytes(),
+            hex,
+        );
+    }
+}
+
+#[test]
+fn base32_unpadded() {
+    check(
+        "base32",
+        &[("variant", "RFC 4648"), ("padding", "false")],
+        b"foobar",
+        "MZXW6YTBOI",
+    );
+}
+
+#[test]
+fn base32_crockford() {
+    check(
+        "base32",
+        &[("variant", "Crockford")],
+        b"foobar",
+        "CSQPYRK1E8",
+    );
+    // lower case, hyphens and the O/I/L aliases
+    let decoder = module("base32-decode", &[("variant", "Crockford")]);
+    assert_eq!(decoder.process(b"csqp-yrkie8").unwrap(), b"foobar");
+    assert_eq!(
+        decoder.process(b"0").unwrap(),
+        decoder.process(b"o").unwrap()
+    );
+}
+
+/// RFC 9285 section 4.
+#[test]
+fn base45() {
+    check("base45", &[], b"AB", "BB8");
+    check("base45", &[], b"Hello!!", "%69 VD92EX0");
+    check("base45", &[], b"base-45", "UJCLQE7W581");
+    check("base45", &[], b"ietf!", "QED8WEX0");
+    let decoder = module("base45-decode", &[]);
+    assert!(decoder.process(b"GGW").is_err());
+    assert!(decoder.process(b"BB8A").is_err());
+}
+
+#[test]
+fn base58() {
+    check("base58", &[], b"Hello World!", "2NEpo7TZRRrLZSi2U");
+    check(
+        "base58",
+        &[("alphabet", "Flickr")],
+        b"Hello World!",
+        "2nePN7syqqRkyrH2t",
+    );
+    check(
+        "base58",
+        &[("alphabet", "Ripple")],
+        b"Hello World!",
+        "p4NFofTZRRiLZS5p7",
+    );
+    // leading zero bytes
+    check("base58", &[], &[0, 0, 1], "112");
+}
+
+/// Version byte 0 and a zero hash: Bitcoin's well-known burn address.
+#[test]
+fn base58_check() {
+    let args = [("alphabet", "Bitcoin"), ("check", "true")];
+    let payload = [0; 21];
+    let encoded = module("base58-encode", &args).process(&payload).unwrap();
+    assert_eq!(encoded, b"1111111111111111111114oLvT2");
+    let decoder = module("base58-decode", &args);
+    assert_eq!(decoder.process(&encoded).unwrap(), payload);
+    let error = decoder.process(b"1111111111111111111114oLvT3").unwrap_err();
+    assert!(error.to_string().contains("checksum"), "{}", error);
+}
+
+#[test]
+fn base62() {
+    check("base62", &[], b"Hello World!", "T8dgcjRGkZ3aysdN");
+    check(
+        "base62",
+        &[("alphabet", "Inverted (0-9a-zA-Z)")],
+        b"Hello World!",
+        "t8DGCJrgKz3AYSDn",
+    );
+}
+
+#[test]
+fn ascii85() {
+    check("base85", &[], b"hello world", "BOu!rD]j7BEbo7");
+    // four zero bytes are shortened to z
+    check(
+        "base85",
+        &[("variant", "Ascii85"), ("delimiters", "true")],
+        b"hello world\0\0\0\0",
+        "<~BOu!rD]j7BEbo7d!!!!~>",
+    );
+    let decoder = module("base85-decode", &[]);
+    assert_eq!(decoder.process(b"z").unwrap(), [0; 4]);
+}
+
+/// The example from the Z85 specification.
+#[test]
+fn z85() {
+    check(
+        "base85",
+        &[("variant", "Z85")],
+        &unhex("864fd26fb559f75b"),
+        "HelloWorld",
+    );
+    // the specification only allows whole groups
+    let error = module("base85-encode", &[("variant", "Z85")])
+        .process(b"hello")
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Z85 encodes whole groups of 4 bytes, the input is 5 bytes long"
+    );
+    let error = module("base85-decode", &[("variant", "Z85")])
+        .process(b"HelloWor")
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid Base85: Z85 needs whole groups of 5 characters, the group at position 5 has 3"
+    );
+}
+
+/// Python's `base64.b85encode`.
+#[test]
+fn git_base85() {
+    check(
+        "base85",
+        &[("variant", "Base85 (git / Python b85)")],
+        b"hello world!?",
+        "Xk~0{Zy<MXa%^NFKL",
+    );
+}
+
+#[test]
+fn base91() {
+    check("base91", &[], b"test", "fPNKd");
+    check("base91", &[], b"Hello World!", ">OwJh>Io0Tv!8PE");
+}
+
+#[test]
+fn invalid_symbol() {
+    let error = module("base58-decode", &[]).process(b"2NE0").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid Base58: '0' at position 3 is not in the alphabet"
+    );
+}
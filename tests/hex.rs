@@ -0,0 +1,145 @@
+//! Tests for To/From Hex and To/From Hexdump. Hexdump layouts match the
+//! output of `xxd`, `hexdump -C` and `od -A x -t x1z`.
+
+mod common;
+
+use common::module;
+
+#[test]
+fn to_hex_delimiters() {
+    for (delimiter, encoded) in [
+        ("None", "4142ff"),
+        ("Space", "41 42 ff"),
+        ("Colon", "41:42:ff"),
+        ("Comma", "41,42,ff"),
+        ("\\x", "\\x41\\x42\\xff"),
+        ("0x", "0x41, 0x42, 0xff"),
+        ("Newline", "41\n42\nff"),
+    ] {
+        let encoder = module("to-hex", &[("delimiter", delimiter)]);
+        assert_eq!(encoder.process(b"AB\xff").unwrap(), encoded.as_bytes());
+        // From Hex detects the delimiter itself
+        let decoder = module("from-hex", &[]);
+        assert_eq!(decoder.process(encoded.as_bytes()).unwrap(), b"AB\xff");
+    }
+}
+
+#[test]
+fn to_hex_lines() {
+    let encoder = module("to-hex", &[("uppercase", "true"), ("bytes_per_line", "4")]);
+    assert_eq!(
+        encoder.process(b"ABCDEFGHIJ\xaa").unwrap(),
+        b"41 42 43 44\n45 46 47 48\n49 4A AA"
+    );
+}
+
+#[test]
+fn from_hex_errors() {
+    let decoder = module("from-hex", &[]);
+    let error = decoder.process(b"41 4").unwrap_err();
+    assert_eq!(error.to_string(), "odd number of hex digits at position 3");
+    let error = decoder.process(b"41 4g").unwrap_err();
+    assert_eq!(error.to_string(), "invalid hex at position 4");
+}
+
+#[test]
+fn to_hexdump_styles() {
+    let input = b"Hello, world!\n\x00\x00xyz";
+    let xxd = module("to-hexdump", &[]);
+    assert_eq!(
+        String::from_utf8(xxd.process(input).unwrap()).unwrap(),
+        "00000000: 4865 6c6c 6f2c 2077 6f72 6c64 210a 0000  Hello, world!...\n\
+         00000010: 7879 7a                                  xyz\n"
+    );
+    let canonical = module("to-hexdump", &[("style", "hexdump -C")]);
+    assert_eq!(
+        String::from_utf8(canonical.process(input).unwrap()).unwrap(),
+        "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 00  |Hello, world!...|\n\
+         00000010  78 79 7a                                          |xyz|\n\
+         00000013\n"
+    );
+    let od = module("to-hexdump", &[("style", "od"), ("width", "8")]);
+    assert_eq!(
+        String::from_utf8(od.process(input).unwrap()).unwrap(),
+        "000000 48 65 6c 6c 6f 2c 20 77  >Hello, w<\n\
+         000008 6f 72 6c 64 21 0a 00 00  >orld!...<\n\
+         000010 78 79 7a                 >xyz<\n\
+         000013\n"
+    );
+}
+
+#[test]
+fn from_hexdump_squeezed() {
+    let decoder = module("from-hexdump", &[]);
+    let expected = [&b"A|B"[..], &[0; 32], b"xyz"].concat();
+    // hexdump -C, with a `|` in the ASCII column
+    let canonical = "00000000  41 7c 42 00 00 00 00 00  00 00 00 00 00 00 00 00  |A|B.............|\n\
+                     00000010  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |................|\n\
+                     *\n\
+                     00000020  00 00 00 78 79 7a                                 |...xyz|\n\
+                     00000026\n";
+    assert_eq!(decoder.process(canonical.as_bytes()).unwrap(), expected);
+    // plain od, whose offsets are octal
+    let od = "0000000 41 7c 42 00 00 00 00 00 00 00 00 00 00 00 00 00\n\
+              0000020 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00\n\
+              *\n\
+              0000040 00 00 00 78 79 7a\n\
+              0000046\n";
+    assert_eq!(decoder.process(od.as_bytes()).unwrap(), expected);
+    // xxd starting at an offset, as with `xxd -s`
+    let xxd = "00000100: 417c 42  A|B\n";
+    assert_eq!(decoder.process(xxd.as_bytes()).unwrap(), b"A|B");
+}
+
+#[test]
+fn from_hexdump_errors() {
+    let decoder = module("from-hexdump", &[]);
+    let error = decoder.process(b"00000000: 41zz  A.\n").unwrap_err();
+    assert_eq!(error.to_string(), "Line 1: '41zz' is not hex");
+    let error = decoder.process(b"00000000  41 42\n*\n").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Line 2: '*' is not followed by an offset"
+    );
+    let error = decoder
+        .process(b"00000000  41 42\n00000005  43\n00000006\n")
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Line 2: offset '00000005' does not follow from the line before"
+    );
+    // plain hex is a bare offset without bytes
+    let error = decoder.process(b"48656c6c6f\n").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "No hex bytes found, the input is not an xxd, hexdump -C or od -t x1 dump"
+    );
+    // but `od` dumps an empty file as just the offset 0
+    assert_eq!(decoder.process(b"000000\n").unwrap(), b"");
+    assert_eq!(decoder.process(b"").unwrap(), b"");
+}
+
+/// Plain `od` prints octal words and `od -x` hex words, both in the byte
+/// order of the machine, so they are refused rather than misread.
+#[test]
+fn from_hexdump_words() {
+    let decoder = module("from-hexdump", &[]);
+    // `printf 'Hello, world!\nabc' | od`
+    let od = "0000000 062510 066154 026157 073440 071157 062154 005041 061141\n\
+              0000020 000143\n\
+              0000021\n";
+    let error = decoder.process(od.as_bytes()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Line 1: '062510' is not a single byte, dump with xxd, hexdump -C or od -t x1"
+    );
+    // the same with `od -x`
+    let od_x = "0000000 6548 6c6c 2c6f 7720 726f 646c 0a21 6261\n\
+                0000020 0063\n\
+                0000021\n";
+    let error = decoder.process(od_x.as_bytes()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Line 1: '6548' is not a single byte, dump with xxd, hexdump -C or od -t x1"
+    );
+}
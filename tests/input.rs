@@ -0,0 +1,69 @@
+//! Decoding byte arguments of each input type, and the errors naming the
+//! field.
+
+use cybercook::modules::input::Argument;
+
+fn decode(value: &str) -> anyhow::Result<Vec<u8>> {
+    Argument::parse(value).try_to_vec("Key")
+}
+
+#[test]
+fn input_types() {
+    for (value, expected) in [
+        ("AB", &b"AB"[..]),
+        ("text:hex:41", b"hex:41"),
+        ("hex:41 42:43,0x44\\x45", b"ABCDE"),
+        ("base64:QUJD RA==", b"ABCD"),
+        ("utf16le:Aé", b"A\x00\xe9\x00"),
+        ("utf16be:Aé", b"\x00A\x00\xe9"),
+        ("utf16le:😀", b"\x3d\xd8\x00\xde"),
+        ("latin1:Aé", b"A\xe9"),
+        ("decimal:65, 66 255,0", b"AB\xff\x00"),
+        (
+            "escaped:a\\x41\\n\\t\\0\\\\\\'\\\"\\u{e9}",
+            b"aA\n\t\x00\\'\"\xc3\xa9",
+        ),
+    ] {
+        assert_eq!(decode(value).unwrap(), expected, "{}", value);
+    }
+}
+
+#[test]
+fn errors_name_the_field() {
+    for (value, error) in [
+        ("hex:41 4g", "Key: invalid hex at position 4"),
+        ("hex:41 423", "Key: odd number of hex digits at position 3"),
+        (
+            "latin1:a€",
+            "Key: '€' at position 1 is not a Latin-1 character",
+        ),
+        (
+            "decimal:1,256",
+            "Key: invalid decimal byte \"256\" at position 2",
+        ),
+        (
+            "decimal:1 -2",
+            "Key: invalid decimal byte \"-2\" at position 2",
+        ),
+        ("escaped:ab\\x4", "Key: invalid escape at position 2"),
+        ("escaped:\\x+1", "Key: invalid escape at position 0"),
+        ("escaped:\\q", "Key: invalid escape at position 0"),
+        ("escaped:\\u{110000}", "Key: invalid escape at position 0"),
+        ("escaped:\\u{41", "Key: invalid escape at position 0"),
+    ] {
+        assert_eq!(decode(value).unwrap_err().to_string(), error, "{}", value);
+    }
+    let error = decode("base64:QUJ!").unwrap_err().to_string();
+    assert!(error.starts_with("Key: invalid Base64: "), "{}", error);
+}
+
+#[test]
+fn file() {
+    let path = std::env::temp_dir().join("cybercook-input-file");
+    std::fs::write(&path, b"\x00\xffkey").unwrap();
+    let value = format!("file:{}", path.display());
+    assert_eq!(decode(&value).unwrap(), b"\x00\xffkey");
+    std::fs::remove_file(&path).unwrap();
+    let error = decode(&value).unwrap_err().to_string();
+    assert!(error.starts_with(&format!("Key: reading {}: ", path.display())));
+}
@@ -0,0 +1,149 @@
+//! `openssl enc` compatibility of the AES passphrase mode. The expected
+//! values were produced with OpenSSL 3.5, e.g.
+//! `openssl enc -aes-256-cbc -pass pass:secret -S 0102030405060708 -md md5`.
+//! With an explicit `-S` OpenSSL omits the `Salted__` header, so it is
+//! prepended here.
+
+mod common;
+
+use common::{module, unhex};
+
+const PLAINTEXT: &[u8] = b"hello openssl\n";
+
+fn salted(salt: &str, ciphertext: &str) -> Vec<u8> {
+    [b"Salted__".to_vec(), unhex(salt), unhex(ciphertext)].concat()
+}
+
+/// Encrypts [`PLAINTEXT`], checks the output, and decrypts it again.
+fn check(args: &[(&str, &str)], expected: &[u8]) {
+    let encrypt = module("aes-encrypt", args);
+    assert_eq!(
+        hex::encode(encrypt.process(PLAINTEXT).unwrap()),
+        hex::encode(expected)
+    );
+    let decrypt = module("aes-decrypt", args);
+    assert_eq!(decrypt.process(expected).unwrap(), PLAINTEXT);
+}
+
+#[test]
+fn bytes_to_key_md5() {
+    check(
+        &[
+            ("kdf", "EVP_BytesToKey MD5"),
+            ("key", "secret"),
+            ("salt", "hex:0102030405060708"),
+        ],
+        &salted("0102030405060708", "98d0923111d9380fddc3cc0df4da8e49"),
+    );
+}
+
+#[test]
+fn bytes_to_key_sha256() {
+    check(
+        &[
+            ("kdf", "EVP_BytesToKey SHA-256"),
+            ("key", "secret"),
+            ("salt", "hex:0102030405060708"),
+        ],
+        &salted("0102030405060708", "6c2c704f29e1e2a81bacf854368d6446"),
+    );
+}
+
+#[test]
+fn pbkdf2() {
+    check(
+        &[
+            ("kdf", "PBKDF2 SHA-256"),
+            ("iterations", "1000"),
+            ("key", "secret"),
+            ("salt", "hex:0102030405060708"),
+        ],
+        &salted("0102030405060708", "ae9dbd93d59cee21018c1e1d746ee894"),
+    );
+}
+
+/// ECB has no IV, so only the key is derived.
+#[test]
+fn pbkdf2_ecb_aes128() {
+    check(
+        &[
+            ("mode", "ECB"),
+            ("kdf", "PBKDF2 SHA-256"),
+            ("key_bits", "AES-128"),
+            ("key", "pw"),
+            ("salt", "hex:a1a2a3a4a5a6a7a8"),
+        ],
+        &salted("a1a2a3a4a5a6a7a8", "ea03c75b4f86c6567f2d70e9b17135c7"),
+    );
+}
+
+#[test]
+fn pbkdf2_stream_modes() {
+    for (mode, bits, ciphertext) in [
+        ("CFB", "AES-192", "e437e4b117af88c2cf0c60b41203"),
+        ("OFB", "AES-256", "4d9f8c6c9a945eb37a331bb36180"),
+        ("CTR", "AES-256", "4d9f8c6c9a945eb37a331bb36180"),
+    ] {
+        check(
+            &[
+                ("mode", mode),
+                ("kdf", "PBKDF2 SHA-256"),
+                ("key_bits", bits),
+                ("key", "pw"),
+                ("salt", "hex:a1a2a3a4a5a6a7a8"),
+            ],
+            &salted("a1a2a3a4a5a6a7a8", ciphertext),
+        );
+    }
+}
+
+/// `openssl enc -nosalt -md md5` writes no header.
+#[test]
+fn no_salt() {
+    check(
+        &[
+            ("kdf", "EVP_BytesToKey MD5"),
+            ("key", "secret"),
+            ("salted", "false"),
+        ],
+        &unhex("dc665e61fefe4b4a1326596669bebcc0"),
+    );
+}
+
+/// Output of `openssl enc -aes-128-cbc -pbkdf2 -iter 5`, with a random salt
+/// in the header.
+#[test]
+fn decrypt_openssl_header() {
+    let decrypt = module(
+        "aes-decrypt",
+        &[
+            ("kdf", "PBKDF2 SHA-256"),
+            ("iterations", "5"),
+            ("key_bits", "AES-128"),
+            ("key", "secret"),
+        ],
+    );
+    let input = unhex("53616c7465645f5f86f51d95fdfc68abd2983eac207bdb5859d27ac2574a7c5c");
+    assert_eq!(decrypt.process(&input).unwrap(), PLAINTEXT);
+}
+
+#[test]
+fn random_salt_round_trips() {
+    let args = [("kdf", "PBKDF2 SHA-256"), ("key", "secret")];
+    let first = module("aes-encrypt", &args).process(PLAINTEXT).unwrap();
+    let second = module("aes-encrypt", &args).process(PLAINTEXT).unwrap();
+    assert!(first.starts_with(b"Salted__"));
+    assert_ne!(first[8..16], second[8..16]);
+    let decrypt = module("aes-decrypt", &args);
+    assert_eq!(decrypt.process(&first).unwrap(), PLAINTEXT);
+}
+
+#[test]
+fn missing_header() {
+    let decrypt = module(
+        "aes-decrypt",
+        &[("kdf", "EVP_BytesToKey SHA-256"), ("key", "secret")],
+    );
+    let error = decrypt.process(&[0; 16]).unwrap_err();
+    assert!(error.to_string().contains("Salted__"), "{}", error);
+}
@@ -0,0 +1,68 @@
+//! Tests for To/From Binary, Octal, Decimal and Base N.
+
+mod common;
+
+use common::module;
+
+/// `Hi!` written by the To module `key`.
+fn to_radix(key: &str, args: &[(&str, &str)]) -> String {
+    String::from_utf8(module(key, args).process(b"Hi!").unwrap()).unwrap()
+}
+
+#[test]
+fn to_radix_layouts() {
+    assert_eq!(to_radix("to-binary", &[]), "01001000 01101001 00100001");
+    assert_eq!(
+        to_radix("to-binary", &[("bits", "4"), ("delimiter", "Comma")]),
+        "0100,1000,0110,1001,0010,0001"
+    );
+    assert_eq!(to_radix("to-octal", &[]), "110 151 041");
+    // the whole input as one stream of octal digits
+    assert_eq!(
+        to_radix("to-octal", &[("bits", "3"), ("delimiter", "None")]),
+        "22064441"
+    );
+    assert_eq!(to_radix("to-decimal", &[]), "72 105 33");
+    assert_eq!(to_radix("to-decimal", &[("pad", "true")]), "072 105 033");
+    assert_eq!(
+        to_radix(
+            "to-base-n",
+            &[
+                ("radix", "16"),
+                ("uppercase", "true"),
+                ("delimiter", "Colon")
+            ]
+        ),
+        "48:69:21"
+    );
+    assert_eq!(to_radix("to-base-n", &[("radix", "5")]), "0242 0410 0113");
+}
+
+#[test]
+fn from_radix_lenient() {
+    let binary = module("from-binary", &[]);
+    assert_eq!(binary.process(b"0b01001000, 0b1101001\n").unwrap(), b"Hi");
+    assert_eq!(binary.process(b"0100100001101001").unwrap(), b"Hi");
+    let decimal = module("from-decimal", &[]);
+    assert_eq!(decimal.process(b"[72, 105; 33]").unwrap(), b"Hi!");
+    let hex = module("from-base-n", &[("radix", "16")]);
+    assert_eq!(hex.process(b"{ 0x48, 0X69 }").unwrap(), b"Hi");
+    let nibbles = module("from-binary", &[("bits", "4")]);
+    assert_eq!(nibbles.process(b"0100 1000 0110 1001").unwrap(), b"Hi");
+}
+
+#[test]
+fn from_radix_errors() {
+    let decimal = module("from-decimal", &[]);
+    let error = decimal.process(b"72 256").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid Decimal: '256' at position 3 does not fit in 8 bits"
+    );
+    let binary = module("from-binary", &[]);
+    let error = binary.process(b"0100100001").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid Binary: '0100100001' at position 0 does not split into groups of 8 digits"
+    );
+}
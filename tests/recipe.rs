@@ -0,0 +1,67 @@
+//! Looking modules up by key, pausing at breakpoints and carrying on from
+//! the pause.
+
+use cybercook::{
+    modules,
+    recipe::{BakeControl, Recipe},
+};
+
+/// Every module is registered under the key it reports.
+#[test]
+fn keys() {
+    for module in modules::all() {
+        let created = modules::create(module.key()).unwrap();
+        assert_eq!(created.key(), module.key());
+    }
+}
+
+/// Recipes name modules by key only, never by display name.
+#[test]
+fn unknown_module() {
+    let error = Recipe::from_json(r#"{"steps": [{"module": "To Hex"}]}"#)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "Unknown module \"To Hex\"");
+}
+
+/// Three steps with a breakpoint before the second and the third.
+fn recipe() -> Recipe {
+    let mut recipe = Recipe::from_keys(["to-hex", "to-hex", "to-hex"]).unwrap();
+    recipe.steps[1].breakpoint = true;
+    recipe.steps[2].breakpoint = true;
+    recipe
+}
+
+#[test]
+fn resume() {
+    let recipe = recipe();
+    let paused = recipe
+        .bake_debug(b"A", None, &BakeControl::default())
+        .unwrap();
+    assert_eq!(paused.paused_at, Some(1));
+    assert_eq!(paused.output, b"41");
+
+    let resumed = recipe
+        .bake_debug(b"A", None, &BakeControl::resume(1))
+        .unwrap();
+    assert_eq!(resumed.paused_at, Some(2));
+    assert_eq!(resumed.output, b"34 31");
+
+    let finished = recipe
+        .bake_debug(b"A", None, &BakeControl::resume(2))
+        .unwrap();
+    assert_eq!(finished.paused_at, None);
+    assert_eq!(finished.output, b"33 34 20 33 31");
+}
+
+/// Running up to the paused step steps over it alone.
+#[test]
+fn step_over() {
+    let mut recipe = recipe();
+    recipe.steps[2].breakpoint = false;
+    let stepped = recipe
+        .bake_debug(b"A", Some(1), &BakeControl::resume(1))
+        .unwrap();
+    assert_eq!(stepped.paused_at, Some(2));
+    assert_eq!(stepped.output, b"34 31");
+}
@@ -0,0 +1,216 @@
+//! Property tests: every encoder/decoder and encrypt/decrypt pair inverts
+//! for random inputs, keys and nonces.
+
+mod common;
+
+use common::module;
+use proptest::prelude::*;
+
+fn hex_arg(bytes: &[u8]) -> String {
+    format!("hex:{}", hex::encode(bytes))
+}
+
+/// Random 16, 24 or 32 byte AES key.
+fn aes_key() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![Just(16), Just(24), Just(32)]
+        .prop_flat_map(|len| prop::collection::vec(any::<u8>(), len))
+}
+
+fn round_trip(encrypt: &str, decrypt: &str, args: &[(&str, &str)], input: &[u8]) -> Vec<u8> {
+    let encrypted = module(encrypt, args).process(input).unwrap();
+    module(decrypt, args).process(&encrypted).unwrap()
+}
+
+proptest! {
+    #[test]
+    fn base64(
+        alphabet in prop::sample::select(vec!["Standard (+/)", "URL-safe (-_)"]),
+        padding in any::<bool>(),
+        wrap in any::<bool>(),
+        input in prop::collection::vec(any::<u8>(), 0..256),
+    ) {
+        let (padding, wrap) = (padding.to_string(), wrap.to_string());
+        let args = [
+            ("alphabet", alphabet),
+            ("padding", padding.as_str()),
+            ("wrap", wrap.as_str()),
+            ("lenient", "true"),
+        ];
+        let encoded = module("base64-encode", &args[..3]).process(&input).unwrap();
+        // the strict decoder reads unwrapped output with the same padding
+        let lenient = wrap.clone();
+        let decoded = module("base64-decode", &[args[0], args[1], ("lenient", &lenient)])
+            .process(&encoded)
+            .unwrap();
+        prop_assert_eq!(decoded, input);
+    }
+
+    #[test]
+    fn base64_custom(
+        alphabet in Just((b'!'..=b'~').filter(|&c| c != b'=').collect::<Vec<u8>>())
+            .prop_shuffle()
+            .prop_map(|shuffled| String::from_utf8(shuffled[..64].to_vec()).unwrap()),
+        input in prop::collection::vec(any::<u8>(), 0..256),
+    ) {
+        let args = [("alphabet", "Custom"), ("custom", alphabet.as_str())];
+        prop_assert_eq!(round_trip("base64-encode", "base64-decode", &args, &input), input);
+    }
+
+    #[test]
+    fn codecs(
+        (codec, variant) in prop::sample::select(vec![
+            ("base32", "RFC 4648"),
+            ("base32", "RFC 4648 hex"),
+            ("base32", "Crockford"),
+            ("base45", ""),
+            ("base58", "Bitcoin"),
+            ("base58", "Ripple"),
+            ("base62", "Standard (0-9A-Za-z)"),
+            ("base62", "Inverted (0-9a-zA-Z)"),
+            ("base85", "Ascii85"),
+            ("base85", "Z85"),
+            ("base85", "Base85 (git / Python b85)"),
+            ("base91", ""),
+        ]),
+        input in prop::collection::vec(any::<u8>(), 0..128),
+    ) {
+        let name = match codec {
+            "base32" | "base85" => "variant",
+            _ => "alphabet",
+        };
+        let args: &[(&str, &str)] = if variant.is_empty() { &[] } else { &[(name, variant)] };
+        // Z85 only encodes whole groups of 4 bytes
+        let input = match variant {
+            "Z85" => &input[..input.len() / 4 * 4],
+            _ => &input[..],
+        };
+        let encode = format!("{}-encode", codec);
+        let decode = format!("{}-decode", codec);
+        prop_assert_eq!(round_trip(&encode, &decode, args, input), input);
+    }
+
+    #[test]
+    fn hex(
+        delimiter in prop::sample::select(vec!["None", "Space", "Colon", "Comma", "\\x", "0x", "Newline"]),
+        uppercase in any::<bool>(),
+        bytes_per_line in 0..20usize,
+        input in prop::collection::vec(any::<u8>(), 0..128),
+    ) {
+        let (uppercase, bytes_per_line) = (uppercase.to_string(), bytes_per_line.to_string());
+        let args = [
+            ("delimiter", delimiter),
+            ("uppercase", uppercase.as_str()),
+            ("bytes_per_line", bytes_per_line.as_str()),
+        ];
+        let encoded = module("to-hex", &args).process(&input).unwrap();
+        prop_assert_eq!(module("from-hex", &[]).process(&encoded).unwrap(), input);
+    }
+
+    #[test]
+    fn hexdump(
+        style in prop::sample::select(vec!["xxd", "hexdump -C", "od"]),
+        width in 1..40usize,
+        input in prop::collection::vec(any::<u8>(), 0..256),
+    ) {
+        let width = width.to_string();
+        let args = [("style", style), ("width", width.as_str())];
+        let dumped = module("to-hexdump", &args).process(&input).unwrap();
+        prop_assert_eq!(module("from-hexdump", &[]).process(&dumped).unwrap(), input);
+    }
+
+    #[test]
+    fn radix(
+        (to, from, radix) in prop::sample::select(vec![
+            ("to-binary", "from-binary", "2"),
+            ("to-octal", "from-octal", "8"),
+            ("to-decimal", "from-decimal", "10"),
+            ("to-base-n", "from-base-n", "3"),
+            ("to-base-n", "from-base-n", "36"),
+        ]),
+        delimiter in prop::sample::select(vec!["None", "Space", "Comma", "Newline"]),
+        bits in 1..=8usize,
+        pad in any::<bool>(),
+        input in prop::collection::vec(any::<u8>(), 0..64),
+    ) {
+        let (bits, pad) = (bits.to_string(), pad.to_string());
+        let mut from_args = vec![("bits", bits.as_str())];
+        if to == "to-base-n" {
+            from_args.push(("radix", radix));
+        }
+        let mut to_args = from_args.clone();
+        to_args.extend([("delimiter", delimiter), ("pad", pad.as_str())]);
+        let encoded = module(to, &to_args).process(&input).unwrap();
+        prop_assert_eq!(module(from, &from_args).process(&encoded).unwrap(), input);
+    }
+
+    #[test]
+    fn base58_check(input in prop::collection::vec(any::<u8>(), 0..64)) {
+        let args = [("check", "true")];
+        prop_assert_eq!(round_trip("base58-encode", "base58-decode", &args, &input), input);
+    }
+
+    #[test]
+    fn aes(
+        mode in prop::sample::select(vec!["CBC", "CFB", "OFB", "CTR", "ECB"]),
+        // zero padding is ambiguous for plaintext ending in zero bytes
+        padding in prop::sample::select(vec!["PKCS#7", "ANSI X.923", "ISO 10126", "ISO/IEC 7816-4"]),
+        key in aes_key(),
+        iv in prop::array::uniform16(any::<u8>()),
+        input in prop::collection::vec(any::<u8>(), 0..256),
+    ) {
+        let args = [
+            ("mode", mode),
+            ("key", &hex_arg(&key)),
+            ("iv", &hex_arg(&iv)),
+            ("padding", padding),
+        ];
+        prop_assert_eq!(round_trip("aes-encrypt", "aes-decrypt", &args, &input), input);
+    }
+
+    #[test]
+    fn aes_no_padding(
+        mode in prop::sample::select(vec!["CBC/NoPadding", "ECB/NoPadding"]),
+        key in aes_key(),
+        iv in prop::array::uniform16(any::<u8>()),
+        blocks in prop::collection::vec(prop::array::uniform16(any::<u8>()), 0..8),
+    ) {
+        let input = blocks.concat();
+        let args = [("mode", mode), ("key", &hex_arg(&key)), ("iv", &hex_arg(&iv))];
+        prop_assert_eq!(round_trip("aes-encrypt", "aes-decrypt", &args, &input), input);
+    }
+
+    #[test]
+    fn aes_passphrase(
+        kdf in prop::sample::select(vec!["EVP_BytesToKey MD5", "EVP_BytesToKey SHA-256", "PBKDF2 SHA-256"]),
+        passphrase in "[ -~]{0,32}",
+        input in prop::collection::vec(any::<u8>(), 0..64),
+    ) {
+        let args = [("kdf", kdf), ("iterations", "10"), ("key", passphrase.as_str())];
+        prop_assert_eq!(round_trip("aes-encrypt", "aes-decrypt", &args, &input), input);
+    }
+
+    #[test]
+    fn aead(
+        // GCM-SIV only takes 128 and 256 bit keys
+        (mode, key) in prop::sample::select(vec!["GCM", "CCM", "EAX", "GCM-SIV"])
+            .prop_flat_map(|mode| {
+                let lengths = if mode == "GCM-SIV" { vec![16, 32] } else { vec![16, 24, 32] };
+                let key = prop::sample::select(lengths)
+                    .prop_flat_map(|len| prop::collection::vec(any::<u8>(), len));
+                (Just(mode), key)
+            }),
+        nonce in prop::collection::vec(any::<u8>(), 16),
+        aad in prop::collection::vec(any::<u8>(), 0..32),
+        input in prop::collection::vec(any::<u8>(), 0..256),
+    ) {
+        // EAX takes a 16 byte nonce, the others 12
+        let nonce = if mode == "EAX" { &nonce[..] } else { &nonce[..12] };
+        let args = [
+            ("mode", mode),
+            ("key", &hex_arg(&key)),
+            ("nonce", &hex_arg(nonce)),
+            ("aad", &hex_arg(&aad)),
+        ];
+        prop_assert_eq!(round_trip("aead-encrypt", "aead-decrypt", &args, &input), input);
+    }
+}